@@ -1,10 +1,78 @@
-use crate::employee::{Employee, EmployeeType};
+use crate::employee::{Employee, EmployeeType, SkillSource};
+use crate::employee::skills::{self, Skills, Work};
+use crate::employee::career::{Level, Review};
+use crate::employee::contract::Contract;
 //use std::arch::x86_64::_MM_FROUND_CUR_DIRECTION;
+use rand::Rng;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt;
 //use super::Software;
 
+pub mod sales;
+pub mod team;
+pub mod org;
+pub mod workplace;
 
+use sales::{Deal, Pipeline, SalesConditions};
+use team::{Mission, Team, TeamType};
+use org::Department;
+use workplace::{Policy, Region};
+use crate::world::timeframe::YearWeek;
+use crate::software::Architecture;
+
+
+// Leads each marketer brings in every week, or consumers each time a campaign lands
+//
+const LEADS_PER_MARKETER: u16 = 2;
+const CONSUMERS_PER_MARKETER: u16 = 10;
+
+// Customers (or hundreds of consumers) one administrator can support
+//
+const SUPPORT_LOAD_PER_ADMINISTRATOR: u32 = 50;
+
+// Morale changes when a colleague is poached or let go and when the annual pay rise comes in
+//
+const POACHED_MORALE: i16 = -5;
+const LAID_OFF_MORALE: i16 = -3;
+const PAY_RISE_MORALE: i16 = 5;
+
+// A week's work teaches a point of learning for every ten points of talent. New hires
+// pick things up twice as fast and somebody much better to learn from adds half as much again.
+//
+const TALENT_PER_LEARNING_POINT: u16 = 10;
+const MENTOR_GAP: u16 = 20;
+
+// A training course - what it costs, what it teaches and how much people like being sent on one
+//
+pub const TRAINING_COST: u32 = 5;
+const TRAINING_LEARNING: u16 = 300;
+const TRAINING_MORALE: i16 = 2;
+
+// Stepping up to run a department - who's ready, and what it's worth to them
+//
+const MANAGEMENT_LEVEL: Level = Level::Senior;
+const MANAGEMENT_RAISE: u32 = 25;
+const MANAGEMENT_MORALE: i16 = 10;
+
+// Contractors cut corners and take what they learn with them. Enough shortcuts add up to a
+// point of technical debt, handing over at the end of a contract leaves a few more behind
+// and they only hold on to half of what a week's work teaches.
+//
+const SHORTCUTS_PER_DEBT: u16 = 20;
+const HANDOVER_SHORTCUTS: u16 = 10;
+const CONTRACTOR_RETENTION: u16 = 50;
+
+// Development effort going into one mission - how many developers and how productive they are
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Capacity {
+    pub developers: u16,                // headcount
+    pub effective_developers: u16,      // what they add up to once coordination and onboarding are paid for
+    pub productivity: u16               // percentage - well run departments can push past 100
+}
+
+#[allow(dead_code)]
 trait SetDirection {
     fn set_direction(&mut self, direction: CompanyDirection);
 }
@@ -15,7 +83,7 @@ trait SetDirection {
 // CompanyDirection is the strategic component of how the company is approaching software development
 //
 //
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Deserialize)]
 pub enum CompanyDirection {
     B2B,
     B2C /* 
@@ -42,9 +110,17 @@ pub enum GrowthStrategy {
     Aggressive*/
 }
 
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Ownership {
-    Private /* ,
-    Public */
+    Private,
+    Public,
+    Acquired
+}
+
+impl fmt::Display for Ownership {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
 }
 
 pub enum HiringStrategy {
@@ -60,11 +136,24 @@ pub enum HiringStrategy {
 pub struct Company {
     _cash_in_bank: u32,                  // starting cash
     _direction: CompanyDirection,
-    _employees: HashMap<String, Employee>,
+    _employees: HashMap<u16, Employee>,  // by ID
+    _next_employee_id: u16,
     _ownership: Ownership,
     _growth_strategy: GrowthStrategy,
     _marketing_strategy: MarketingStrategy,
-    _hiring_strategy: HiringStrategy
+    _hiring_strategy: HiringStrategy,
+    _upgrade_focus: u16,                 // percentage of dev capacity spent on dependency upgrades
+    _sales_pipeline: Pipeline,
+    _funding_raised: u32,                // everything investors have put in
+    _teams: Vec<Team>,                   // anyone not in a team just picks up whatever product work is going
+    _next_team_id: u16,
+    _reviews_enabled: bool,              // do we hold annual reviews at all?
+    _pending_bonuses: u32,               // awarded at review time, paid with the next payroll
+    _shortcuts: u16,                     // corners contractors have cut that aren't technical debt yet
+    _policy: Policy,                     // where people are expected to work
+    _offices: Vec<Region>,               // headquarters first
+    _hiring_region: Region,              // where new people are taken on
+    _hiring_role: EmployeeType           // who we're recruiting next
 }
 
 impl Company {
@@ -73,10 +162,23 @@ impl Company {
         return Company { _cash_in_bank: cash_in_bank,
                          _direction: direction,
                          _employees: HashMap::new(),
+                         _next_employee_id: 1,
                          _ownership: Ownership::Private,
                          _growth_strategy: GrowthStrategy::Focussed,
                          _marketing_strategy: MarketingStrategy::Organic,
-                         _hiring_strategy: HiringStrategy::Passive
+                         _hiring_strategy: HiringStrategy::Passive,
+                         _upgrade_focus: 0,
+                         _sales_pipeline: Pipeline::new(),
+                         _funding_raised: 0,
+                         _teams: Vec::new(),
+                         _next_team_id: 1,
+                         _reviews_enabled: true,
+                         _pending_bonuses: 0,
+                         _shortcuts: 0,
+                         _policy: Policy::Office,
+                         _offices: vec![workplace::HEADQUARTERS],
+                         _hiring_region: workplace::HEADQUARTERS,
+                         _hiring_role: EmployeeType::Developer
                      };
     }
 
@@ -99,6 +201,21 @@ impl Company {
         }
     }
 
+    pub fn upgrade_focus(&self) -> u16 {
+        self._upgrade_focus
+    }
+
+    // Cycle through how much development time goes on keeping dependencies up to date
+    pub fn cycle_upgrade_focus(&mut self)
+    {
+        self._upgrade_focus = match self._upgrade_focus {
+            0 => 10,
+            10 => 25,
+            25 => 50,
+            _ => 0
+        }
+    }
+
     // Do we need to zero out or instead just go negative?
     //
     pub fn remove_cash(&mut self, cash :u32) {
@@ -115,11 +232,458 @@ impl Company {
         self._cash_in_bank += cash;
     }
 
-    pub fn add_employee(&mut self, employee :Employee) {
-        self._employees.insert(employee.name(), employee);
+    pub fn policy(&self) -> Policy {
+        self._policy
+    }
+
+    // Change where people work - everybody feels the difference straight away. If we can
+    // no longer hire where we were, new people start at headquarters again.
+    //
+    pub fn set_policy(&mut self, policy: Policy) {
+        self.adjust_morale(policy.morale() - self._policy.morale());
+        self._policy = policy;
+
+        if !self.can_hire_in(self._hiring_region) {
+            self._hiring_region = workplace::HEADQUARTERS;
+        }
+    }
+
+    pub fn cycle_policy(&mut self) {
+        self.set_policy(self._policy.next())
+    }
+
+    pub fn offices(&self) -> &Vec<Region> {
+        &self._offices
+    }
+
+    // Somewhere we don't have an office yet
+    //
+    pub fn next_office(&self) -> Option<Region> {
+        Region::all().into_iter().find(|region| !self._offices.contains(region))
+    }
+
+    pub fn open_office(&mut self, region: Region) -> Result<(), String> {
+        if self._offices.contains(&region) {
+            return Err(format!("We already have an office in {}", region))
+        }
+        if self._cash_in_bank < workplace::OFFICE_FIT_OUT {
+            return Err(format!("Can't afford to fit out an office in {}", region))
+        }
+
+        self._cash_in_bank -= workplace::OFFICE_FIT_OUT;
+        self.add_office(region);
+        return Ok(())
+    }
+
+    // An office that's already up and running
+    //
+    pub fn add_office(&mut self, region: Region) {
+        if !self._offices.contains(&region) {
+            self._offices.push(region);
+        }
+    }
+
+    pub fn hiring_region(&self) -> Region {
+        self._hiring_region
+    }
+
+    // People need an office to go to - unless nobody does
+    //
+    pub fn can_hire_in(&self, region: Region) -> bool {
+        self._policy == Policy::Remote || self._offices.contains(&region)
+    }
+
+    pub fn cycle_hiring_region(&mut self) {
+        let regions = Region::all();
+        let current = regions.iter().position(|region| *region == self._hiring_region).unwrap_or(0);
+        if let Some(region) = (1..=regions.len()).map(|step| regions[(current + step) % regions.len()]).find(|region| self.can_hire_in(*region)) {
+            self._hiring_region = region;
+        }
+    }
+
+    pub fn hiring_role(&self) -> EmployeeType {
+        self._hiring_role
+    }
+
+    // Executives are promoted from within - everybody else we can recruit for
+    //
+    pub fn cycle_hiring_role(&mut self) {
+        let roles: Vec<EmployeeType> = EmployeeType::all().iter().copied().filter(|role| !org::is_executive(*role)).collect();
+        let current = roles.iter().position(|role| *role == self._hiring_role).unwrap_or(0);
+        self._hiring_role = roles[(current + 1) % roles.len()];
+    }
+
+    // Rent on every office, with a desk for everyone based there who needs one - a month
+    //
+    pub fn office_costs(&self) -> u32 {
+        self._offices.iter().map(|region| {
+            let people = self._employees.values().filter(|employee| employee.region() == *region).count() as u32;
+            workplace::office_lease(*region, people, self._policy)
+        }).sum()
+    }
+
+    // How well people work together given where they are (percentage)
+    //
+    pub fn collaboration(&self) -> u16 {
+        let mut sites: Vec<Region> = self._employees.values().map(|employee| employee.region()).collect();
+        sites.sort_by_key(|region| Region::all().iter().position(|other| other == region));
+        sites.dedup();
+        workplace::collaboration(self._policy, sites.len())
+    }
+
+    pub fn ownership(&self) -> Ownership {
+        self._ownership
+    }
+
+    pub fn set_ownership(&mut self, ownership: Ownership) {
+        self._ownership = ownership
+    }
+
+    pub fn funding_raised(&self) -> u32 {
+        self._funding_raised
+    }
+
+    // Share options held by everybody still here
+    //
+    pub fn options_granted(&self) -> u32 {
+        self._employees.values().map(|employee| employee.equity()).sum()
+    }
+
+    // Investment is cash in the bank that we have to account for
+    //
+    pub fn raise_funding(&mut self, funding :u32) {
+        self._cash_in_bank = self._cash_in_bank.saturating_add(funding);
+        self._funding_raised = self._funding_raised.saturating_add(funding);
+    }
+
+    // Everybody who joins gets the next ID - names can be shared but IDs never are. New
+    // people report to the head of their department, or the CEO if there isn't one.
+    // Returns the ID they were given.
+    //
+    pub fn add_employee(&mut self, mut employee :Employee) -> u16 {
+        let id = self._next_employee_id;
+        self._next_employee_id += 1;
+
+        let employee_type = employee.employee_type();
+        employee.set_id(id);
+        self._employees.insert(id, employee);
+        self.set_default_manager(id);
+
+        // A new executive takes over from whoever was filling in
+        //
+        if org::is_executive(employee_type) {
+            let ceos = self.ids_of(EmployeeType::CEO);
+            let filled_in: Vec<u16> = self._employees.values()
+                                          .filter(|employee| employee.id() != id)
+                                          .filter(|employee| employee.manager().is_none_or(|manager| ceos.contains(&manager)))
+                                          .map(|employee| employee.id())
+                                          .collect();
+            for other in filled_in {
+                self.set_default_manager(other);
+            }
+        }
+
+        id
+    }
+
+    pub fn employee(&self, id: u16) -> Option<&Employee> {
+        self._employees.get(&id)
+    }
+
+    // The first person to join with this name
+    //
+    #[cfg(test)]
+    pub fn employee_named(&self, name: &str) -> Option<&Employee> {
+        self._employees.values().filter(|employee| employee.name() == name).min_by_key(|employee| employee.id())
+    }
+
+    // Name of somebody for messages - falls back to their ID if they've gone
+    //
+    pub fn name_of(&self, id: u16) -> String {
+        self.employee(id).map(|employee| employee.name()).unwrap_or(format!("#{}", id))
+    }
+
+    // IDs of everyone of a type, longest serving first
+    //
+    fn ids_of(&self, employee_type: EmployeeType) -> Vec<u16> {
+        let mut ids: Vec<u16> = self._employees.values().filter(|employee| employee.employee_type() == employee_type).map(|employee| employee.id()).collect();
+        ids.sort();
+        ids
+    }
+
+    fn set_default_manager(&mut self, id: u16) {
+        let employee_type = match self._employees.get(&id) {
+            Some(employee) => employee.employee_type(),
+            None => return
+        };
+
+        let heads = match employee_type {
+            EmployeeType::CEO => Vec::new(),
+            executive if org::is_executive(executive) => self.ids_of(EmployeeType::CEO),
+            staff => {
+                let heads = self.ids_of(Department::of(staff).head());
+                if heads.is_empty() { self.ids_of(EmployeeType::CEO) } else { heads }
+            }
+        };
+
+        let manager = heads.into_iter().find(|head| *head != id);
+        if let Some(employee) = self._employees.get_mut(&id) {
+            employee.set_manager(manager);
+        }
     }
 
-    pub fn get_employees(&self) -> &HashMap<String, Employee> {
+    // Change who somebody reports to - nobody can end up managing their own manager
+    //
+    pub fn set_manager(&mut self, id: u16, manager: Option<u16>) -> Result<(), String> {
+        if !self._employees.contains_key(&id) {
+            return Err(format!("Nobody with ID {} works here", id))
+        }
+
+        if let Some(manager) = manager {
+            if !self._employees.contains_key(&manager) {
+                return Err(format!("Nobody with ID {} works here", manager))
+            }
+            if manager == id || self.reporting_line(manager).contains(&id) {
+                return Err(format!("{} can't report to {} - {} is in their reporting line", self.name_of(id), self.name_of(manager), self.name_of(manager)))
+            }
+        }
+
+        if let Some(employee) = self._employees.get_mut(&id) {
+            employee.set_manager(manager);
+        }
+        return Ok(())
+    }
+
+    // Everyone above somebody, nearest first
+    //
+    pub fn reporting_line(&self, id: u16) -> Vec<u16> {
+        let mut line = Vec::new();
+        let mut current = self._employees.get(&id).and_then(|employee| employee.manager());
+
+        while let Some(manager) = current {
+            if line.contains(&manager) {
+                break
+            }
+            current = self._employees.get(&manager).and_then(|employee| employee.manager());
+            line.push(manager);
+        }
+
+        line
+    }
+
+    // Direct reports, in name order
+    //
+    pub fn reports(&self, id: u16) -> Vec<&Employee> {
+        let mut reports: Vec<&Employee> = self._employees.values().filter(|employee| employee.manager() == Some(id)).collect();
+        reports.sort_by_key(|employee| (employee.name(), employee.id()));
+        reports
+    }
+
+    // Somebody leaving hands their reports up to their own manager
+    //
+    fn remove_from_org(&mut self, leaver: &Employee) {
+        for employee in self._employees.values_mut() {
+            if employee.manager() == Some(leaver.id()) {
+                employee.set_manager(leaver.manager());
+            }
+        }
+    }
+
+    // How well a department is working (percentage). Managers with too many or too few
+    // reports cost their people and an executive in charge lifts everyone.
+    //
+    pub fn department_effectiveness(&self, department: Department) -> u16 {
+        let mut spans: HashMap<u16, usize> = HashMap::new();
+        for manager in self._employees.values().filter_map(|employee| employee.manager()) {
+            *spans.entry(manager).or_insert(0) += 1;
+        }
+
+        let overheads: Vec<u16> = self._employees.values()
+                                      .filter(|employee| Department::of(employee.employee_type()) == department)
+                                      .map(|employee| employee.manager().map(|manager| org::span_overhead(spans[&manager])).unwrap_or(0))
+                                      .collect();
+
+        org::effectiveness(&overheads, !self.ids_of(department.head()).is_empty())
+    }
+
+    // Our most talented individual contributor - the one everybody else wants to hire.
+    // The executive team stays loyal and contractors are already somebody else's.
+    //
+    pub fn key_employee(&self) -> Option<u16> {
+        self.staff()
+            .max_by_key(|employee| (employee.talent(), std::cmp::Reverse(employee.id())))
+            .map(|employee| employee.id())
+    }
+
+    // Whoever is getting the least done
+    //
+    pub fn weakest_employee(&self) -> Option<u16> {
+        self.staff()
+            .min_by_key(|employee| (employee.efficiency(), employee.id()))
+            .map(|employee| employee.id())
+    }
+
+    // Permanent people below the executive team
+    //
+    fn staff(&self) -> impl Iterator<Item = &Employee> {
+        self._employees.values()
+            .filter(|employee| !matches!(employee.employee_type(), EmployeeType::CEO | EmployeeType::CTO | EmployeeType::CMO | EmployeeType::CPO | EmployeeType::FinanceDirector))
+            .filter(|employee| !employee.is_contractor())
+    }
+
+    // A competitor tempts away our key employee
+    //
+    pub fn poach_employee(&mut self) -> Option<Employee> {
+        let poached = self.key_employee().and_then(|id| self._employees.remove(&id));
+
+        // Watching a good colleague walk out knocks everyone else and leaves a gap in their team
+        //
+        if let Some(employee) = &poached {
+            self.adjust_morale(POACHED_MORALE);
+            self.unassign_employee(employee.id());
+            self.remove_from_org(employee);
+        }
+
+        poached
+    }
+
+    // Cost cutting - the weakest performer goes and everybody else wonders who's next
+    //
+    pub fn lay_off_weakest_employee(&mut self) -> Option<Employee> {
+        let laid_off = self.weakest_employee().and_then(|id| self._employees.remove(&id));
+
+        if let Some(employee) = &laid_off {
+            self.adjust_morale(LAID_OFF_MORALE);
+            self.unassign_employee(employee.id());
+            self.remove_from_org(employee);
+        }
+
+        laid_off
+    }
+
+    pub fn adjust_morale(&mut self, change: i16) {
+        for employee in self._employees.values_mut() {
+            employee.adjust_morale(change);
+        }
+    }
+
+    // How everybody is feeling on average (0 - 100)
+    //
+    pub fn average_morale(&self) -> u16 {
+        match self._employees.len() {
+            0 => 0,
+            employees => ( self._employees.values().map(|employee| employee.morale() as u32).sum::<u32>() / employees as u32 ) as u16
+        }
+    }
+
+    // Give the key employee a percentage pay rise to keep them
+    //
+    pub fn raise_key_employee(&mut self, percentage: u16) {
+        if let Some(employee) = self.key_employee().and_then(|id| self._employees.get_mut(&id)) {
+            employee.add_compensation(employee.compensation() * percentage as u32 / 100);
+            employee.adjust_morale(percentage as i16 / 2);
+        }
+    }
+
+    // Everybody's pay goes up by the annual inflation percentage - contract rates are
+    // fixed for the term
+    //
+    pub fn apply_salary_inflation(&mut self, percentage: u16) {
+        for employee in self._employees.values_mut().filter(|employee| !employee.is_contractor()) {
+            employee.add_compensation(employee.compensation() * percentage as u32 / 100);
+            employee.adjust_morale(PAY_RISE_MORALE);
+        }
+    }
+
+    pub fn reviews_enabled(&self) -> bool {
+        self._reviews_enabled
+    }
+
+    pub fn toggle_reviews(&mut self) {
+        self._reviews_enabled = !self._reviews_enabled
+    }
+
+    pub fn pending_bonuses(&self) -> u32 {
+        self._pending_bonuses
+    }
+
+    // Year end reviews for everyone on the payroll who's been here long enough to judge -
+    // returns who got what, by ID
+    //
+    pub fn conduct_reviews(&mut self, yearweek: YearWeek) -> Vec<(u16, Review)> {
+        let mut reviews: Vec<(u16, Review)> = self._employees.values_mut()
+                                                  .filter(|employee| !employee.is_onboarding() && !employee.is_contractor())
+                                                  .map(|employee| (employee.id(), employee.review(yearweek)))
+                                                  .collect();
+        reviews.sort_by_key(|(id, _)| *id);
+
+        self._pending_bonuses += reviews.iter().map(|(_, review)| review.bonus).sum::<u32>();
+        reviews
+    }
+
+    pub fn skip_reviews(&mut self) {
+        for employee in self._employees.values_mut().filter(|employee| !employee.is_onboarding() && !employee.is_contractor()) {
+            employee.skip_review();
+        }
+    }
+
+    // The strongest senior person in a department with nobody running it - returns them
+    // and the role they'd step into
+    //
+    pub fn management_candidate(&self) -> Option<(u16, EmployeeType)> {
+        Department::all().iter()
+            .filter(|department| **department != Department::Executive && self.ids_of(department.head()).is_empty())
+            .filter_map(|department| self._employees.values()
+                                         .filter(|employee| Department::of(employee.employee_type()) == *department && employee.level() >= MANAGEMENT_LEVEL)
+                                         .filter(|employee| !employee.is_contractor())
+                                         .max_by_key(|employee| (employee.level(), employee.efficiency(), std::cmp::Reverse(employee.id())))
+                                         .map(|employee| (employee.id(), department.head())))
+            .next()
+    }
+
+    // Make somebody the head of their department. They join the executive team and
+    // everyone in the department now reports to them.
+    //
+    pub fn promote_to_management(&mut self, id: u16) -> Result<EmployeeType, String> {
+        let employee = self._employees.get(&id).ok_or(format!("Nobody with ID {} works here", id))?;
+        let head = Department::of(employee.employee_type()).head();
+
+        if org::is_executive(employee.employee_type()) {
+            return Err(format!("{} is already running a department", employee.name()))
+        }
+        if employee.is_contractor() {
+            return Err(format!("{} is a contractor - they'd have to join us first", employee.name()))
+        }
+        if employee.level() < MANAGEMENT_LEVEL {
+            return Err(format!("{} is {} - they need to be at least {} to manage", employee.name(), employee.level(), MANAGEMENT_LEVEL))
+        }
+        if !self.ids_of(head).is_empty() {
+            return Err(format!("There's already a {:?}", head))
+        }
+
+        if let Some(employee) = self._employees.get_mut(&id) {
+            employee.set_employee_type(head);
+            employee.add_compensation(employee.compensation() * MANAGEMENT_RAISE / 100);
+            employee.adjust_morale(MANAGEMENT_MORALE);
+        }
+
+        // They answer to the CEO now and take over the department, including anyone the
+        // CEO was looking after
+        //
+        self.set_default_manager(id);
+        let department: Vec<u16> = self._employees.values()
+                                       .filter(|employee| employee.id() != id && Department::of(employee.employee_type()) == Department::of(head))
+                                       .map(|employee| employee.id())
+                                       .collect();
+        for other in department {
+            self.set_default_manager(other);
+        }
+
+        self.recalculate_team_productivity();
+        return Ok(head)
+    }
+
+    pub fn get_employees(&self) -> &HashMap<u16, Employee> {
         &self._employees
     }
 
@@ -134,18 +698,337 @@ impl Company {
         return employees;
     }
 
-    // Queue up the payroll for execution - how do we work out bonuses and year end type stuff?
-    // Also when do we work out performance raises etc?
+    pub fn teams(&self) -> &Vec<Team> {
+        &self._teams
+    }
+
+    pub fn team(&self, id: u16) -> Option<&Team> {
+        self._teams.iter().find(|team| team.id() == id)
+    }
+
+    // Which team, if any, somebody is in
+    //
+    pub fn team_of(&self, id: u16) -> Option<u16> {
+        self._teams.iter().find(|team| team.has_member(id)).map(|team| team.id())
+    }
+
+    // Everybody not in a team, in name order
+    //
+    pub fn unassigned_employees(&self) -> Vec<&Employee> {
+        let mut unassigned: Vec<&Employee> = self._employees.values().filter(|employee| self.team_of(employee.id()).is_none()).collect();
+        unassigned.sort_by_key(|employee| (employee.name(), employee.id()));
+        unassigned
+    }
+
+    pub fn create_team(&mut self, name: String, team_type: TeamType, mission: Mission, yearweek: YearWeek) -> u16 {
+        let id = self._next_team_id;
+        self._next_team_id += 1;
+        self._teams.push(Team::new(name, id, team_type, mission, yearweek));
+        id
+    }
+
+    // Break a team up - everybody in it goes back to being unassigned
+    //
+    pub fn disband_team(&mut self, id: u16) -> Option<Team> {
+        let index = self._teams.iter().position(|team| team.id() == id)?;
+        Some(self._teams.remove(index))
+    }
+
+    // Move somebody into a team, out of whichever team they were in before
+    //
+    pub fn assign_employee(&mut self, id: u16, team_id: u16, yearweek: YearWeek) -> Result<(), String> {
+        if !self._employees.contains_key(&id) {
+            return Err(format!("Nobody with ID {} works here", id))
+        }
+        if self.team(team_id).is_none() {
+            return Err(format!("There's no team {}", team_id))
+        }
+
+        self.unassign_employee(id);
+        if let Some(team) = self._teams.iter_mut().find(|team| team.id() == team_id) {
+            team.add_member(id, yearweek);
+        }
+        self.recalculate_team_productivity();
+
+        return Ok(())
+    }
+
+    pub fn unassign_employee(&mut self, id: u16) {
+        for team in self._teams.iter_mut() {
+            team.remove_member(id);
+        }
+        self.recalculate_team_productivity();
+    }
+
+    pub fn set_team_mission(&mut self, id: u16, mission: Mission, yearweek: YearWeek) {
+        if let Some(team) = self._teams.iter_mut().find(|team| team.id() == id) {
+            team.set_mission(mission, yearweek);
+        }
+    }
+
+    pub fn set_team_type(&mut self, id: u16, team_type: TeamType, yearweek: YearWeek) {
+        if let Some(team) = self._teams.iter_mut().find(|team| team.id() == id) {
+            team.set_team_type(team_type, yearweek);
+        }
+        self.recalculate_team_productivity();
+    }
+
+    pub fn recalculate_team_productivity(&mut self) {
+        for team in self._teams.iter_mut() {
+            team.recalculate_productivity(&self._employees);
+        }
+    }
+
+    pub fn sales_pipeline(&self) -> &Pipeline {
+        &self._sales_pipeline
+    }
+
+    // Consumers marketing brings in on top of word of mouth whenever we gain some
+    //
+    pub fn consumer_signups(&self) -> u32 {
+        match self._direction {
+            CompanyDirection::B2C => self.campaign(CONSUMERS_PER_MARKETER),
+            CompanyDirection::B2B => 0
+        }
+    }
+
+    // What a campaign brings in - marketing gets more out of one with the skills to run it
+    //
+    fn campaign(&self, per_marketer: u16) -> u32 {
+        let marketers: Vec<&Skills> = self._employees.values()
+                                          .filter(|employee| [EmployeeType::Marketeer, EmployeeType::CMO].contains(&employee.employee_type()))
+                                          .map(|employee| employee.skills())
+                                          .collect();
+        let fit_factor = skills::fit_factor(skills::fit(Work::Marketing, &marketers)) as u32;
+        marketers.len() as u32 * per_marketer as u32 * self.department_effectiveness(Department::Marketing) as u32 / 100 * fit_factor / 100
+    }
+
+    // Weekly sales move - marketing generates leads for a B2B business and salespeople
+    // work them through the pipeline. Returns the deals we won.
+    //
+    pub fn update_sales_pipeline<R: Rng>(&mut self, conditions: &SalesConditions, rng: &mut R) -> Vec<Deal> {
+
+        if self._direction == CompanyDirection::B2B {
+            let leads = self.campaign(LEADS_PER_MARKETER);
+            self._sales_pipeline.generate_leads(leads as u16, rng);
+        }
+
+        // Salespeople sit in finance and sell as well as the department runs and their own
+        // selling skills allow
+        //
+        let effectiveness = self.department_effectiveness(Department::Finance) as u32;
+        let salespeople: Vec<(u16, u16)> = self._employees.values()
+                                               .filter(|employee| employee.employee_type() == EmployeeType::Salesperson)
+                                               .map(|employee| {
+                                                   let fit_factor = skills::fit_factor(skills::fit(Work::Selling, &[employee.skills()])) as u32;
+                                                   (employee.id(), ( employee.talent() as u32 * effectiveness / 100 * fit_factor / 100 ) as u16)
+                                               })
+                                               .collect();
+
+        return self._sales_pipeline.update_week(&salespeople, conditions, rng)
+    }
+
+    // Take somebody on - unlike the people we start with they need time to get up to speed,
+    // and they join feeling however the workplace policy makes everybody else feel
+    //
+    pub fn hire_employee(&mut self, mut employee :Employee) -> u16 {
+        employee.start_onboarding();
+        employee.adjust_morale(self._policy.morale());
+        self.add_employee(employee)
+    }
+
+    pub fn progress_onboarding(&mut self) {
+        for employee in self._employees.values_mut() {
+            employee.progress_onboarding();
+        }
+    }
+
+    // Bring somebody in on a fixed term - they're quicker to get going than a hire but the
+    // supplier's rate is on top of their pay
+    //
+    pub fn engage_contractor(&mut self, mut employee :Employee, contract :Contract) -> u16 {
+        employee.add_compensation(employee.compensation() * ( contract.supplier.rate() - 100 ) / 100);
+        employee.start_onboarding_for(contract.supplier.onboarding_weeks());
+        employee.adjust_morale(self._policy.morale());
+        employee.set_contract(Some(contract));
+        self.add_employee(employee)
+    }
+
+    // Keep a contractor on for another term - returns when they'll now leave
+    //
+    pub fn extend_contract(&mut self, id: u16) -> Result<YearWeek, String> {
+        let employee = self._employees.get_mut(&id).ok_or(format!("Nobody with ID {} works here", id))?;
+        let mut contract = employee.contract().ok_or(format!("{} is on the payroll, not a contract", employee.name()))?;
+
+        contract.extend();
+        employee.set_contract(Some(contract));
+        return Ok(contract.ends)
+    }
+
+    pub fn contractors(&self) -> Vec<&Employee> {
+        self._employees.values().filter(|employee| employee.is_contractor()).collect()
+    }
+
+    // Everyone whose term is up leaves, along with whatever they knew about the code -
+    // returns who went
+    //
+    pub fn end_contracts(&mut self, yearweek: YearWeek) -> Vec<Employee> {
+        let mut ended: Vec<u16> = self._employees.values()
+                                      .filter(|employee| employee.contract().is_some_and(|contract| contract.has_ended(yearweek)))
+                                      .map(|employee| employee.id())
+                                      .collect();
+        ended.sort();
+
+        let leavers: Vec<Employee> = ended.iter().filter_map(|id| self._employees.remove(id)).collect();
+        for leaver in leavers.iter() {
+            self.unassign_employee(leaver.id());
+            self.remove_from_org(leaver);
+            if leaver.employee_type() == EmployeeType::Developer {
+                self._shortcuts += HANDOVER_SHORTCUTS;
+            }
+        }
+
+        if !leavers.is_empty() {
+            self.recalculate_team_productivity();
+        }
+        leavers
+    }
+
+    // A week of corners cut by contract developers - returns the technical debt it has
+    // added up to
+    //
+    pub fn take_contract_debt(&mut self) -> u16 {
+        self._shortcuts += self._employees.values()
+                               .filter(|employee| employee.employee_type() == EmployeeType::Developer)
+                               .filter_map(|employee| employee.contract())
+                               .map(|contract| contract.supplier.shortcuts())
+                               .sum::<u16>();
+
+        let debt = self._shortcuts / SHORTCUTS_PER_DEBT;
+        self._shortcuts %= SHORTCUTS_PER_DEBT;
+        debt
+    }
+
+    // Is there somebody much better in their team, or above them, to learn from? Contractors
+    // don't stay long enough to pass on what they know.
+    //
+    fn has_mentor(&self, employee: &Employee) -> bool {
+        let mut mentors: Vec<&Employee> = self.team_of(employee.id()).and_then(|id| self.team(id))
+                                              .map(|team| team.members().iter().filter_map(|id| self._employees.get(id)).collect())
+                                              .unwrap_or_default();
+        mentors.extend(employee.manager().and_then(|manager| self._employees.get(&manager)));
+
+        mentors.iter().any(|mentor| !mentor.is_contractor() && mentor.efficiency() >= employee.efficiency() + MENTOR_GAP)
+    }
+
+    // What somebody spends their week doing - their team's mission, or whatever their
+    // role usually does if they're not in one
+    //
+    pub fn work_of(&self, employee: &Employee) -> Work {
+        match self.team_of(employee.id()).and_then(|id| self.team(id)) {
+            Some(team) => team.mission().work(),
+            None => Work::typical(employee.employee_type())
+        }
+    }
+
+    // A week's work makes everybody a little better, at the skill they use most - returns
+    // who improved
+    //
+    pub fn develop_skills(&mut self, yearweek: YearWeek) -> Vec<u16> {
+        let learning: Vec<(u16, u16, SkillSource, Work)> = self._employees.values().map(|employee| {
+            let work = self.work_of(employee);
+            let mut points = employee.talent() / TALENT_PER_LEARNING_POINT;
+            if employee.is_onboarding() {
+                points *= 2;
+            }
+            if employee.is_contractor() {
+                points = points * CONTRACTOR_RETENTION / 100;
+            }
+
+            match self.has_mentor(employee) {
+                true => (employee.id(), points + points / 2, SkillSource::Mentoring, work),
+                false => (employee.id(), points, SkillSource::Experience, work)
+            }
+        }).collect();
+
+        let mut improved: Vec<u16> = Vec::new();
+        for (id, points, source, work) in learning {
+            if let Some(employee) = self._employees.get_mut(&id) {
+                let gained = employee.learn(points, source, yearweek);
+                if gained > 0 {
+                    let skill = work.exercises(employee.skills());
+                    employee.improve_skill(skill, gained);
+                    improved.push(id);
+                }
+            }
+        }
+        improved.sort();
+        self.recalculate_team_productivity();
+        improved
+    }
+
+    // Pay for somebody to go on a course - returns the efficiency they gained
+    //
+    pub fn send_on_training(&mut self, id: u16, yearweek: YearWeek) -> Result<u16, String> {
+        let work = self._employees.get(&id).map(|employee| self.work_of(employee)).ok_or(format!("Nobody with ID {} works here", id))?;
+        if self._cash_in_bank < TRAINING_COST {
+            return Err(format!("Can't afford to send {} on a course", self.name_of(id)))
+        }
+
+        let employee = self._employees.get_mut(&id).ok_or(format!("Nobody with ID {} works here", id))?;
+        self._cash_in_bank -= TRAINING_COST;
+        employee.adjust_morale(TRAINING_MORALE);
+        let gained = employee.learn(TRAINING_LEARNING, SkillSource::Training, yearweek);
+        employee.improve_skill(work.exercises(employee.skills()), gained);
+
+        self.recalculate_team_productivity();
+        return Ok(gained)
+    }
+
+    pub fn roll_sales_month(&mut self) {
+        self._sales_pipeline.roll_month();
+    }
+
+    // How quickly do we get back to customers? (0 - 100)
+    //
+    // Administrators pick up support and each can look after a fixed load of customers
+    pub fn get_support_responsiveness(&self, support_load: u32) -> u16 {
+        if support_load == 0 {
+            return 100
+        }
+
+        // Administrators outside an operations team still answer the phones, and everyone in
+        // one pitches in as well as the team works
+        //
+        let administrators = self._employees.values()
+                                 .filter(|employee| employee.employee_type() == EmployeeType::Administrator)
+                                 .filter(|employee| self.team_of(employee.id()).and_then(|id| self.team(id)).is_none_or(|team| team.mission() != Mission::Operations))
+                                 .count() as u32;
+        let operations = self._teams.iter().filter(|team| team.mission() == Mission::Operations)
+                                            .map(|team| team.get_size() as u32 * team.productivity() as u32)
+                                            .sum::<u32>();
+
+        let support_capacity = administrators * SUPPORT_LOAD_PER_ADMINISTRATOR + operations * SUPPORT_LOAD_PER_ADMINISTRATOR / 100;
+
+        return u32::min(100, support_capacity * 100 / support_load) as u16
+    }
+
+    // Queue up the payroll for execution. Raises come out of the annual reviews and land in
+    // everyone's salary; bonuses from the reviews are paid once, with the next payroll. The
+    // rent on our offices goes out at the same time.
     //
     pub fn queue_payroll(&mut self) -> bool {
         // In the simple case we just execute it
         //
         let mut payroll_amount :u32 = 0;
 
-        // First add it up
+        // First add it up - salespeople get their commission on top
         for (_key, val) in self._employees.iter() {
             payroll_amount += val.salary()
         }
+        payroll_amount += self._sales_pipeline.take_commission();
+        payroll_amount += std::mem::take(&mut self._pending_bonuses);
+        payroll_amount += self.office_costs();
 
         if payroll_amount > self.cash_in_bank() {
             self._cash_in_bank = 0;
@@ -156,50 +1039,94 @@ impl Company {
         return true;
     }
 
-    // What is our development capacity?
+    // What is our development capacity for a mission?
     //
-    // This is a function of developers, reliability, 
-    pub fn get_development_capacity(&self, reliability: u16, quality: u16) -> u16 {
+    // Developers in teams with that mission work as well as their team does. Developers
+    // nobody has put in a team muddle along on product work together. Poor quality and
+    // reliability then slow everyone down.
+    //
+    // How much actually gets done depends on more than headcount - bigger teams spend more
+    // time talking, new hires take a while to contribute and more teams means more
+    // treading on each other's toes, depending on the architecture.
+    pub fn get_development_capacity(&self, mission: Mission, reliability: u16, quality: u16, architecture: Architecture) -> Capacity {
 
-        // Count developers and their levels
+        // Everybody writing code - teams with a development mission and the unassigned developers
         //
+        let unassigned = developers_in(&self.unassigned_employees());
+        let mut groups: Vec<(Mission, Vec<&Employee>, u16, u16)> = Vec::new();
 
-        // https://doc.rust-lang.org/std/iter/trait.IntoIterator.html#tymethod.into_iter
-        let mut developer_skills = 0;
-        let mut developers = 0;
+        for team in self._teams.iter().filter(|team| team.mission() != Mission::Operations) {
+            let members: Vec<&Employee> = team.members().iter().filter_map(|id| self._employees.get(id)).collect();
+            let onboarding = members.iter().filter(|member| member.is_onboarding()).count();
+            let developers = developers_in(&members);
+            if !developers.is_empty() {
+                groups.push((team.mission(), developers, team.productivity(), team::coordination_overhead(members.len(), onboarding)));
+            }
+        }
 
+        if !unassigned.is_empty() {
+            let onboarding = unassigned.iter().filter(|member| member.is_onboarding()).count();
+            groups.push((Mission::Product, unassigned.clone(), team::productivity(TeamType::Etc, Mission::Product, &unassigned), team::coordination_overhead(unassigned.len(), onboarding)));
+        }
 
-        for (_key, val) in self._employees.iter() {
-            if val.employee_type() == EmployeeType::Developer {
-                developers += 1;
-                developer_skills += val.efficiency();        
-            }
+        let cross_team = team::cross_team_overhead(groups.len(), architecture.team_independence()) as f32;
+
+        let mut developers: u32 = 0;
+        let mut effective_developers: f32 = 0.0;
+        let mut weighted_productivity: u32 = 0;
+
+        for (_mission, group, team_productivity, overhead) in groups.iter().filter(|(group_mission, ..)| *group_mission == mission) {
+            developers += group.len() as u32;
+            weighted_productivity += group.len() as u32 * *team_productivity as u32;
+
+            let ramped_up = group.iter().map(|developer| developer.ramp_up() as f32 / 100.0f32).sum::<f32>();
+            effective_developers += ramped_up * ( 100.0f32 - *overhead as f32 ) / 100.0f32 * ( 100.0f32 - cross_team ) / 100.0f32;
+        }
+
+        let mut productivity = match developers {
+            0 => 0,
+            _ => ( weighted_productivity / developers ) as u16
         };
 
-        if developers > 0 {
-            developer_skills = ( developer_skills as f32 / developers as f32 ) as u16; 
+        // Engineering has to be well run - feature work needs product management as well
+        //
+        productivity = ( productivity as u32 * self.department_effectiveness(Department::Engineering) as u32 / 100 ) as u16;
+        if mission == Mission::Product {
+            productivity = ( productivity as u32 * self.department_effectiveness(Department::Product) as u32 / 100 ) as u16;
         }
 
-        // Got number of developers and combined average skills
+        // People who aren't in the same room don't work together as well
         //
+        productivity = ( productivity as u32 * self.collaboration() as u32 / 100 ) as u16;
+
         if quality < 50 {
-            developer_skills /= 2;
+            productivity /= 2;
         }
 
         // Adjust by reliability
         //
         if reliability < 30 {
-            developer_skills /= 4;
+            productivity /= 4;
         } else if reliability < 70 {
-            developer_skills /= 2;
+            productivity /= 2;
         }
 
-        return developer_skills;
-            
+        // Anybody at all still gets something done
+        //
+        let effective_developers = match developers {
+            0 => 0,
+            _ => u16::max(1, effective_developers.round() as u16)
+        };
+
+        return Capacity { developers: developers as u16, effective_developers, productivity }
     }
 
 }
 
+fn developers_in<'a>(members: &[&'a Employee]) -> Vec<&'a Employee> {
+    members.iter().filter(|employee| employee.employee_type() == EmployeeType::Developer).copied().collect()
+}
+
 impl SetDirection for Company {
     fn set_direction(&mut self, direction: CompanyDirection) {
         //Company { cash_in_bank: self.cash_in_bank, customers: self.customers, cost_of_service_per_month: self.cost_of_service_per_month, direction: direction }
@@ -210,14 +1137,21 @@ impl SetDirection for Company {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::employee::skills::Skill;
+    use crate::employee::contract::Supplier;
 
     #[test]
     fn company_direction_test() {
 
         let mut company = Company::new(100, CompanyDirection::B2B);
 
+        company.add_employee(Employee::new(EmployeeType::Marketeer, "Marketeer 1".to_string(), 32, 55, 90, 70));
+        assert_eq!(company.consumer_signups(), 0);
+
+        // Marketing brings consumers along instead of business leads
         company.set_direction(CompanyDirection::B2C);
         assert_eq!(company.direction(), CompanyDirection::B2C);
+        assert!(company.consumer_signups() > 0);
     }
 
     #[test]
@@ -232,4 +1166,318 @@ mod test {
         assert_eq!(company.cash_in_bank(), 100);
     }
 
+    #[test]
+    fn company_support_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        assert_eq!(company.get_support_responsiveness(0), 100);
+        assert_eq!(company.get_support_responsiveness(10), 0);
+
+        company.add_employee(Employee::new(EmployeeType::Administrator, "Admin 1".to_string(), 37, 80, 80, 65));
+        assert_eq!(company.get_support_responsiveness(100), 50);
+    }
+
+    #[test]
+    fn company_poach_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        company.add_employee(Employee::new(EmployeeType::CTO, "CTO".to_string(), 45, 150, 90, 99));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77));
+
+        assert_eq!(company.poach_employee().unwrap().name(), "Developer 1");
+        assert_eq!(company.average_morale(), 65);
+        assert_eq!(company.poach_employee().unwrap().name(), "Developer 2");
+        assert!(company.poach_employee().is_none());
+    }
+
+    #[test]
+    fn company_lay_off_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        company.add_employee(Employee::new(EmployeeType::CTO, "CTO".to_string(), 45, 150, 10, 99));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77));
+
+        // The least efficient goes, however talented - the executive team is safe
+        assert_eq!(company.lay_off_weakest_employee().unwrap().name(), "Developer 2");
+        assert_eq!(company.average_morale(), 67);
+        assert_eq!(company.lay_off_weakest_employee().unwrap().name(), "Developer 1");
+        assert!(company.lay_off_weakest_employee().is_none());
+    }
+
+    #[test]
+    fn company_salary_inflation_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let developer = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 1200, 100, 85));
+
+        company.apply_salary_inflation(5);
+        assert_eq!(company.employee(developer).unwrap().compensation(), 1260);
+    }
+
+    #[test]
+    fn company_ids_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let first = company.add_employee(Employee::new(EmployeeType::Developer, "Ada Lovelace".to_string(), 30, 70, 100, 85));
+        let second = company.add_employee(Employee::new(EmployeeType::Tester, "Ada Lovelace".to_string(), 28, 50, 90, 70));
+
+        // Sharing a name doesn't make you the same person
+        assert_ne!(first, second);
+        assert_eq!(company.get_employees().len(), 2);
+        assert_eq!(company.employee(second).unwrap().id(), second);
+        assert_eq!(company.employee_named("Ada Lovelace").unwrap().id(), first);
+        assert_eq!(company.name_of(99), "#99");
+
+        // IDs aren't handed out again once somebody has left
+        company.poach_employee();
+        let third = company.add_employee(Employee::new(EmployeeType::Developer, "Alan Turing".to_string(), 30, 70, 100, 85));
+        assert!(third > second);
+    }
+
+    #[test]
+    fn company_contractors_test() {
+
+        let starts = YearWeek::new(2000, 1).unwrap();
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let permanent = company.add_employee(Employee::new(EmployeeType::Developer, "Ada Lovelace".to_string(), 30, 70, 100, 85));
+        let contractor = company.engage_contractor(Employee::new(EmployeeType::Developer, "Alan Turing".to_string(), 30, 70, 100, 99),
+                                                   Contract::new(Supplier::Agency, starts));
+
+        // Twice the price, nearly up to speed and nobody's idea of a key employee
+        let employee = company.employee(contractor).unwrap();
+        assert_eq!((employee.compensation(), employee.onboarding()), (140, 1));
+        assert_eq!(company.key_employee(), Some(permanent));
+        assert_eq!(company.conduct_reviews(starts).len(), 1);
+
+        // Five weeks of agency code is a point of technical debt
+        let debt: u16 = (0..5).map(|_| company.take_contract_debt()).sum();
+        assert_eq!(debt, 1);
+
+        assert_eq!(company.extend_contract(contractor), Ok(YearWeek::new(2000, 27).unwrap()));
+        assert!(company.extend_contract(permanent).is_err());
+        assert!(company.end_contracts(YearWeek::new(2000, 26).unwrap()).is_empty());
+
+        // They go when the term is up and the handover leaves a few shortcuts behind
+        let leavers = company.end_contracts(YearWeek::new(2000, 27).unwrap());
+        assert_eq!(leavers.len(), 1);
+        assert_eq!(company.get_employees().len(), 1);
+        assert_eq!(company._shortcuts, HANDOVER_SHORTCUTS);
+    }
+
+    #[test]
+    fn company_workplace_test() {
+
+        let mut company = Company::new(200, CompanyDirection::B2B);
+        for number in 0..10 {
+            company.add_employee(Employee::new(EmployeeType::Developer, format!("Developer {}", number), 30, 120, 100, 85));
+        }
+        assert_eq!(company.office_costs(), 11);
+        assert!(company.queue_payroll());
+        assert_eq!(company.cash_in_bank(), 200 - 100 - 11);
+
+        // Half the desks, happier people who don't work together quite as well
+        company.set_policy(Policy::Hybrid);
+        assert_eq!(company.office_costs(), 7);
+        assert_eq!(company.average_morale(), 78);
+        assert_eq!(company.collaboration(), 95);
+
+        // Hiring in Berlin needs an office there first
+        assert!(!company.can_hire_in(Region::Berlin));
+        assert_eq!(company.next_office(), Some(Region::Berlin));
+        assert!(company.open_office(Region::Berlin).is_ok());
+        assert!(company.open_office(Region::Berlin).is_err());
+        company.cycle_hiring_region();
+        assert_eq!(company.hiring_region(), Region::Berlin);
+
+        let mut employee = Employee::new(EmployeeType::Tester, "Tester".to_string(), 28, 50, 90, 70);
+        employee.set_region(Region::Berlin);
+        company.add_employee(employee);
+        assert_eq!(company.collaboration(), 90);
+        assert_eq!(company.office_costs(), 7 + 1);
+
+        // Going remote stops the splitting mattering and lets us hire anywhere
+        company.set_policy(Policy::Remote);
+        assert_eq!(company.collaboration(), 85);
+        assert!(company.can_hire_in(Region::Bangalore));
+
+        // Somebody new feels the same way about it as everybody else
+        let hire = company.hire_employee(Employee::new(EmployeeType::Tester, "Tester 2".to_string(), 28, 50, 90, 70));
+        assert_eq!(company.employee(hire).unwrap().morale(), 70 + Policy::Remote.morale() as u16);
+    }
+
+    #[test]
+    fn company_teams_test() {
+
+        let yearweek = YearWeek::new(2000, 1).unwrap();
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let developer_1 = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        let developer_2 = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 30, 70, 80, 85));
+        let tester = company.add_employee(Employee::new(EmployeeType::Tester, "Tester 1".to_string(), 28, 50, 90, 70));
+
+        // Nobody in a team - the developers muddle along on product work
+        assert_eq!(company.get_development_capacity(Mission::Product, 100, 100, Architecture::ProofofConcept), Capacity { developers: 2, effective_developers: 2, productivity: 80 });
+
+        let product = company.create_team("Thundercats".to_string(), TeamType::Agile, Mission::Product, yearweek);
+        let platform = company.create_team("Avengers".to_string(), TeamType::Platform, Mission::Platform, yearweek);
+        assert!(company.assign_employee(99, product, yearweek).is_err());
+        company.assign_employee(developer_1, product, yearweek).unwrap();
+        company.assign_employee(tester, product, yearweek).unwrap();
+        company.assign_employee(developer_2, platform, yearweek).unwrap();
+        assert!(company.unassigned_employees().is_empty());
+
+        // The agile team has no product owner but the tester rounds out its skills. A lone
+        // developer is short of the security know-how platform work needs.
+        assert_eq!(company.get_development_capacity(Mission::Product, 100, 100, Architecture::ProofofConcept), Capacity { developers: 1, effective_developers: 1, productivity: 84 });
+        assert_eq!(company.get_development_capacity(Mission::Platform, 100, 100, Architecture::ProofofConcept), Capacity { developers: 1, effective_developers: 1, productivity: 68 });
+
+        // Moving team takes you out of the old one
+        company.assign_employee(developer_2, product, yearweek).unwrap();
+        assert_eq!(company.team(platform).unwrap().get_size(), 0);
+
+        company.disband_team(product);
+        assert_eq!(company.unassigned_employees().len(), 3);
+        assert_eq!(company.team_of(developer_1), None);
+    }
+
+    #[test]
+    fn company_brooks_law_test() {
+
+        let yearweek = YearWeek::new(2000, 1).unwrap();
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let developers: Vec<u16> = (1..=20).map(|number| company.add_employee(Employee::new(EmployeeType::Developer, format!("Developer {:02}", number), 30, 70, 100, 85)))
+                                           .collect();
+
+        // Twenty developers all talking to each other get a fraction of twenty developers' work done
+        let capacity = |company: &Company, architecture: Architecture| company.get_development_capacity(Mission::Product, 100, 100, architecture).effective_developers;
+        assert_eq!(capacity(&company, Architecture::Monolith), 5);
+
+        // Four teams of five do better, and better still when the architecture keeps them out of each other's way
+        for team in 0..4 {
+            let id = company.create_team(format!("Team {}", team), TeamType::Platform, Mission::Product, yearweek);
+            for member in developers[team * 5..team * 5 + 5].iter() {
+                company.assign_employee(*member, id, yearweek).unwrap();
+            }
+        }
+        assert_eq!(capacity(&company, Architecture::Monolith), 15);
+        assert_eq!(capacity(&company, Architecture::Microservices), 16);
+
+        // A new hire adds nothing at first - what they manage goes on slowing their team down
+        let hire = company.hire_employee(Employee::new(EmployeeType::Developer, "Developer 21".to_string(), 30, 70, 100, 85));
+        company.assign_employee(hire, 1, yearweek).unwrap();
+        assert_eq!(capacity(&company, Architecture::Microservices), 16);
+
+        for _week in 0..crate::employee::ONBOARDING_WEEKS {
+            company.progress_onboarding();
+        }
+        assert_eq!(capacity(&company, Architecture::Microservices), 17);
+    }
+
+    #[test]
+    fn company_skills_test() {
+
+        let yearweek = YearWeek::new(2000, 1).unwrap();
+        let mut company = Company::new(TRAINING_COST, CompanyDirection::B2B);
+        let developer_1 = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        let developer_2 = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 30, 70, 60, 80));
+        let developer_3 = company.hire_employee(Employee::new(EmployeeType::Developer, "Developer 3".to_string(), 30, 70, 60, 80));
+
+        // Ten weeks of experience, nobody to learn from
+        for _week in 0..10 {
+            company.develop_skills(yearweek);
+        }
+        assert_eq!(company.employee(developer_2).unwrap().efficiency(), 60);
+        assert_eq!(company.employee(developer_3).unwrap().efficiency(), 61);
+        assert_eq!(company.employee(developer_3).unwrap().skills().level(Skill::Backend), 61);
+
+        // In a team with somebody much better they learn faster
+        let id = company.create_team("Thundercats".to_string(), TeamType::Agile, Mission::Product, yearweek);
+        company.assign_employee(developer_1, id, yearweek).unwrap();
+        company.assign_employee(developer_2, id, yearweek).unwrap();
+        for _week in 0..10 {
+            company.develop_skills(yearweek);
+        }
+        assert_eq!(company.employee(developer_2).unwrap().efficiency(), 62);
+        assert_eq!(company.employee(developer_2).unwrap().skill_history().last().unwrap().source, SkillSource::Mentoring);
+
+        // Courses cost money
+        assert_eq!(company.send_on_training(developer_2, yearweek), Ok(3));
+        assert_eq!(company.cash_in_bank(), 0);
+        assert_eq!(company.employee(developer_2).unwrap().skills().level(Skill::Backend), 65);
+        assert!(company.send_on_training(developer_2, yearweek).is_err());
+    }
+
+    #[test]
+    fn company_reviews_test() {
+
+        let yearweek = YearWeek::new(2000, 52).unwrap();
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let developer_1 = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 106, 85));
+        let developer_2 = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 30, 70, 70, 85));
+        company.hire_employee(Employee::new(EmployeeType::Developer, "Developer 3".to_string(), 30, 70, 70, 85));
+
+        // The new hire is too new to review and everyone else is doing what their level expects
+        let reviews = company.conduct_reviews(yearweek);
+        assert_eq!(reviews.iter().map(|(id, _)| *id).collect::<Vec<u16>>(), vec![developer_1, developer_2]);
+        assert_eq!(company.employee(developer_1).unwrap().compensation(), 72);
+        assert_eq!(company.pending_bonuses(), 6);
+
+        // Bonuses go out once, with the next payroll and the rent
+        assert!(company.queue_payroll());
+        assert_eq!(company.cash_in_bank(), 100 - 6 - 6 - 5 - 6 - company.office_costs());
+        assert_eq!(company.pending_bonuses(), 0);
+
+        // Nobody runs engineering so the strongest senior developer steps up
+        assert!(company.promote_to_management(developer_2).is_err());
+        assert_eq!(company.management_candidate(), Some((developer_1, EmployeeType::CTO)));
+        assert_eq!(company.promote_to_management(developer_1), Ok(EmployeeType::CTO));
+        assert_eq!(company.employee(developer_2).unwrap().manager(), Some(developer_1));
+        assert_eq!(company.management_candidate(), None);
+    }
+
+    #[test]
+    fn company_org_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let ada = company.add_employee(Employee::new(EmployeeType::CEO, "Ada".to_string(), 45, 150, 100, 90));
+        let developers: Vec<u16> = (1..=10).map(|number| company.add_employee(Employee::new(EmployeeType::Developer, format!("Developer {}", number), 30, 70, 100, 85)))
+                                           .collect();
+
+        // Everybody reports to the CEO until there's a CTO - ten reports is too many
+        assert_eq!(company.reports(ada).len(), 10);
+        assert_eq!(company.department_effectiveness(Department::Engineering), 90);
+
+        let grace = company.add_employee(Employee::new(EmployeeType::CTO, "Grace".to_string(), 45, 150, 100, 90));
+        assert_eq!(company.reports(ada).len(), 1);
+        assert_eq!(company.reporting_line(developers[2]), vec![grace, ada]);
+
+        // Nobody manages their own manager
+        assert!(company.set_manager(grace, Some(developers[2])).is_err());
+        company.set_manager(developers[0], Some(developers[1])).unwrap();
+        company.set_manager(developers[2], Some(developers[1])).unwrap();
+        company.set_manager(developers[3], Some(developers[1])).unwrap();
+        assert_eq!(company.reports(grace).len(), 7);
+
+        // When Developer 2 leaves their reports go back to the CTO
+        let leaver = company._employees.remove(&developers[1]).unwrap();
+        company.remove_from_org(&leaver);
+        assert_eq!(company.reports(grace).len(), 9);
+    }
+
+    #[test]
+    fn company_upgrade_focus_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        assert_eq!(company.upgrade_focus(), 0);
+
+        company.cycle_upgrade_focus();
+        company.cycle_upgrade_focus();
+        assert_eq!(company.upgrade_focus(), 25);
+
+        company.cycle_upgrade_focus();
+        company.cycle_upgrade_focus();
+        assert_eq!(company.upgrade_focus(), 0);
+    }
+
 }
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::employee::EmployeeType;
+
+// Reports a manager can look after properly. Outside this range management either
+// can't keep up or gets in the way.
+//
+pub const MIN_SPAN: usize = 3;
+pub const MAX_SPAN: usize = 8;
+
+// Effectiveness lost for each report over the widest span and for each one short
+// of the narrowest
+//
+const WIDE_SPAN_OVERHEAD: u16 = 5;
+const NARROW_SPAN_OVERHEAD: u16 = 2;
+const MAX_OVERHEAD: u16 = 50;
+
+// What a department gains from having an executive in charge
+//
+const EXECUTIVE_BONUS: u16 = 10;
+
+// Departments from the org chart in employee.rs
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Department {
+    Executive,
+    Engineering,
+    Finance,
+    Product,
+    Marketing
+}
+
+impl Department {
+
+    pub fn all() -> [Department; 5] {
+        [Department::Executive, Department::Engineering, Department::Finance, Department::Product, Department::Marketing]
+    }
+
+    pub fn of(employee_type: EmployeeType) -> Department {
+        match employee_type {
+            EmployeeType::CEO => Department::Executive,
+            EmployeeType::CTO | EmployeeType::Developer | EmployeeType::Tester | EmployeeType::Administrator => Department::Engineering,
+            EmployeeType::FinanceDirector | EmployeeType::Accountant | EmployeeType::Salesperson => Department::Finance,
+            EmployeeType::CPO | EmployeeType::ProductOwner | EmployeeType::ProductManager => Department::Product,
+            EmployeeType::CMO | EmployeeType::Marketeer => Department::Marketing
+        }
+    }
+
+    // The executive who runs the department
+    //
+    pub fn head(&self) -> EmployeeType {
+        match self {
+            Department::Executive => EmployeeType::CEO,
+            Department::Engineering => EmployeeType::CTO,
+            Department::Finance => EmployeeType::FinanceDirector,
+            Department::Product => EmployeeType::CPO,
+            Department::Marketing => EmployeeType::CMO
+        }
+    }
+}
+
+impl fmt::Display for Department {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub fn is_executive(employee_type: EmployeeType) -> bool {
+    Department::all().iter().any(|department| department.head() == employee_type)
+}
+
+// Percentage of effectiveness lost by the reports of a manager with this many of them
+//
+pub fn span_overhead(span: usize) -> u16 {
+    let overhead = if span > MAX_SPAN {
+        ( span - MAX_SPAN ) as u16 * WIDE_SPAN_OVERHEAD
+    } else if span > 0 && span < MIN_SPAN {
+        ( MIN_SPAN - span ) as u16 * NARROW_SPAN_OVERHEAD
+    } else {
+        0
+    };
+
+    u16::min(MAX_OVERHEAD, overhead)
+}
+
+// How well a department works (percentage) given the overhead each member suffers
+// from their manager's span and whether there's an executive in charge
+//
+pub fn effectiveness(overheads: &[u16], has_head: bool) -> u16 {
+    let average_overhead = match overheads.len() {
+        0 => 0,
+        members => overheads.iter().map(|overhead| *overhead as u32).sum::<u32>() / members as u32
+    } as u16;
+
+    let bonus = if has_head { EXECUTIVE_BONUS } else { 0 };
+
+    100 - average_overhead + bonus
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn span_overhead_test() {
+        assert_eq!(span_overhead(0), 0);
+        assert_eq!(span_overhead(1), 4);
+        assert_eq!(span_overhead(MIN_SPAN), 0);
+        assert_eq!(span_overhead(MAX_SPAN), 0);
+        assert_eq!(span_overhead(MAX_SPAN + 2), 10);
+        assert_eq!(span_overhead(100), MAX_OVERHEAD);
+    }
+
+    #[test]
+    fn department_test() {
+        assert_eq!(Department::of(EmployeeType::Salesperson), Department::Finance);
+        assert_eq!(Department::of(EmployeeType::Tester).head(), EmployeeType::CTO);
+        assert!(is_executive(EmployeeType::CMO));
+        assert!(!is_executive(EmployeeType::ProductOwner));
+
+        assert_eq!(effectiveness(&[], false), 100);
+        assert_eq!(effectiveness(&[10, 0], true), 105);
+    }
+
+}
@@ -0,0 +1,400 @@
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::software::customers::Segment;
+
+// Monthly new business each salesperson is expected to bring in
+//
+pub const MONTHLY_QUOTA: u32 = 2000;
+
+// Open deals a single salesperson can work at once
+//
+const DEALS_PER_SALESPERSON: usize = 8;
+
+// Weeks a lead will wait for somebody to call them back
+//
+const LEAD_PATIENCE_WEEKS: u16 = 4;
+
+// Where a deal is in the B2B sales process
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DealStage {
+    Lead,
+    Qualified,
+    Demo,
+    Proposal,
+    Negotiation,
+    Won,
+    Lost
+}
+
+impl DealStage {
+
+    // Weeks a deal typically sits in each stage - enterprise deals take twice as long
+    //
+    fn weeks(&self, segment: Segment) -> u16 {
+        let weeks = match self {
+            DealStage::Lead => 1,
+            DealStage::Qualified => 2,
+            DealStage::Demo => 2,
+            DealStage::Proposal => 2,
+            DealStage::Negotiation => 3,
+            DealStage::Won | DealStage::Lost => 0
+        };
+
+        if segment == Segment::Enterprise {
+            return weeks * 2
+        }
+
+        weeks
+    }
+
+    fn next(&self) -> DealStage {
+        match self {
+            DealStage::Lead => DealStage::Qualified,
+            DealStage::Qualified => DealStage::Demo,
+            DealStage::Demo => DealStage::Proposal,
+            DealStage::Proposal => DealStage::Negotiation,
+            stage => *stage
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        *self != DealStage::Won && *self != DealStage::Lost
+    }
+}
+
+impl fmt::Display for DealStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// What the market looks like to a buyer this week
+//
+#[derive(Debug, Copy, Clone)]
+pub struct SalesConditions {
+    pub feature_richness: u16,  // 0 - 100
+    pub competition: u16,       // 0 - 1000
+    pub competitor_price: u16,  // percentage of our list price
+    pub customer_budget: u16    // percentage of normal spending (50 - 150)
+}
+
+
+pub struct Deal {
+    _id: u32,
+    _segment: Segment,
+    _seats: u32,
+    _discount: u16,             // percentage off list price
+    _stage: DealStage,
+    _weeks_in_stage: u16,
+    _owner: Option<u16>         // salesperson employee id
+}
+
+impl Deal {
+
+    pub fn new(id: u32, segment: Segment, seats: u32) -> Deal {
+        Deal { _id: id, _segment: segment, _seats: seats, _discount: 0, _stage: DealStage::Lead, _weeks_in_stage: 0, _owner: None }
+    }
+
+    #[allow(dead_code)]
+    pub fn id(&self) -> u32 {
+        self._id
+    }
+
+    pub fn segment(&self) -> Segment {
+        self._segment
+    }
+
+    pub fn seats(&self) -> u32 {
+        self._seats
+    }
+
+    pub fn stage(&self) -> DealStage {
+        self._stage
+    }
+
+    pub fn owner(&self) -> Option<u16> {
+        self._owner
+    }
+
+    // Monthly contract value after discount
+    //
+    pub fn value(&self) -> u32 {
+        self._seats * self._segment.list_price_per_seat() * ( 100 - self._discount as u32 ) / 100
+    }
+
+    // Chance of closing (0 - 95) from how well the product fits, what it costs against
+    // the competition, what buyers can spend, who else is pitching and how good our
+    // salesperson is
+    //
+    pub fn win_probability(&self, conditions: &SalesConditions, salesperson_talent: u16) -> u16 {
+        let feature_fit = u32::min(100, conditions.feature_richness as u32 * 100 / self._segment.feature_requirement() as u32) as i32;
+        let our_price = 100 - self._discount as i32;
+
+        let probability = 20
+                        + feature_fit * 3 / 10
+                        + u16::min(100, salesperson_talent) as i32 / 4
+                        + ( conditions.competitor_price as i32 - our_price ) / 2
+                        + ( conditions.customer_budget as i32 - 100 ) / 5
+                        - u16::min(1000, conditions.competition) as i32 / 40;
+
+        i32::clamp(probability, 5, 95) as u16
+    }
+}
+
+
+pub struct Pipeline {
+    _deals: Vec<Deal>,
+    _next_id: u32,
+    _booked_this_month: HashMap<u16, u32>,  // new monthly value closed by each salesperson
+    _commission_due: u32,                   // paid out with the next payroll
+    _won: u32,
+    _lost: u32
+}
+
+impl Pipeline {
+
+    pub fn new() -> Pipeline {
+        Pipeline { _deals: Vec::new(), _next_id: 1, _booked_this_month: HashMap::new(), _commission_due: 0, _won: 0, _lost: 0 }
+    }
+
+    #[cfg(test)]
+    pub fn deals(&self) -> &Vec<Deal> {
+        &self._deals
+    }
+
+    pub fn open_deals(&self) -> usize {
+        self._deals.iter().filter(|deal| deal.stage().is_open()).count()
+    }
+
+    pub fn pipeline_value(&self) -> u32 {
+        self._deals.iter().filter(|deal| deal.stage().is_open()).map(|deal| deal.value()).sum()
+    }
+
+    pub fn won(&self) -> u32 {
+        self._won
+    }
+
+    pub fn lost(&self) -> u32 {
+        self._lost
+    }
+
+    pub fn commission_due(&self) -> u32 {
+        self._commission_due
+    }
+
+    pub fn booked_this_month(&self, salesperson: u16) -> u32 {
+        *self._booked_this_month.get(&salesperson).unwrap_or(&0)
+    }
+
+    // Marketing brings in leads - mostly small businesses
+    //
+    pub fn generate_leads<R: Rng>(&mut self, leads: u16, rng: &mut R) {
+        for _lead in 0..leads {
+            let rand_segment: u16 = rng.gen_range(0, 100);
+            let segment = if rand_segment < 60 {
+                Segment::SMB
+            } else if rand_segment < 90 {
+                Segment::MidMarket
+            } else {
+                Segment::Enterprise
+            };
+
+            let (min_seats, max_seats) = segment.typical_seats();
+            let seats = rng.gen_range(min_seats, max_seats + 1);
+
+            self._deals.push(Deal::new(self._next_id, segment, seats));
+            self._next_id += 1;
+        }
+    }
+
+    // Weekly move of every deal. Salespeople (id, talent) pick up leads up to their capacity,
+    // owned deals move along the stages and close or fall out at the end.
+    //
+    // Returns the deals won this week so they can become customers.
+    //
+    pub fn update_week<R: Rng>(&mut self, salespeople: &[(u16, u16)], conditions: &SalesConditions, rng: &mut R) -> Vec<Deal> {
+
+        self.assign_leads(salespeople);
+
+        for deal in self._deals.iter_mut().filter(|deal| deal.stage().is_open()) {
+            deal._weeks_in_stage += 1;
+
+            let owner_talent = deal.owner().and_then(|owner| salespeople.iter().find(|(id, _talent)| *id == owner).map(|(_id, talent)| *talent));
+
+            let talent = match owner_talent {
+                Some(talent) => talent,
+                None => {
+                    // Nobody is working it - if the salesperson left it goes back in the pool,
+                    // otherwise the lead eventually goes cold
+                    if deal.owner().is_some() {
+                        deal._owner = None;
+                        deal._weeks_in_stage = 0;
+                    } else if deal._weeks_in_stage > LEAD_PATIENCE_WEEKS {
+                        deal._stage = DealStage::Lost;
+                    }
+                    continue;
+                }
+            };
+
+            if deal._weeks_in_stage < deal.stage().weeks(deal.segment()) {
+                continue;
+            }
+
+            let probability = deal.win_probability(conditions, talent);
+
+            match deal.stage() {
+                // A good share of leads just aren't a fit
+                DealStage::Lead => {
+                    if rng.gen_range(0, 100) < 70 {
+                        deal._stage = DealStage::Qualified;
+                    } else {
+                        deal._stage = DealStage::Lost;
+                    }
+                },
+                DealStage::Negotiation => {
+                    if rng.gen_range(0, 100) < probability {
+                        deal._stage = DealStage::Won;
+                    } else if deal._discount < 30 {
+                        // Try again with a sweetener
+                        deal._discount += 10;
+                    } else {
+                        deal._stage = DealStage::Lost;
+                    }
+                },
+                stage => deal._stage = stage.next()
+            }
+
+            deal._weeks_in_stage = 0;
+        }
+
+        // Book what we won and pay commission
+        //
+        let mut won = Vec::new();
+        let mut index = 0;
+        while index < self._deals.len() {
+            match self._deals[index].stage() {
+                DealStage::Won => {
+                    let deal = self._deals.remove(index);
+                    self.book(&deal);
+                    won.push(deal);
+                },
+                DealStage::Lost => {
+                    self._deals.remove(index);
+                    self._lost += 1;
+                },
+                _ => index += 1
+            }
+        }
+
+        return won
+    }
+
+    fn assign_leads(&mut self, salespeople: &[(u16, u16)]) {
+        if salespeople.is_empty() {
+            return
+        }
+
+        let mut load: HashMap<u16, usize> = salespeople.iter().map(|(id, _talent)| (*id, 0)).collect();
+        for deal in self._deals.iter().filter(|deal| deal.stage().is_open()) {
+            if let Some(count) = deal.owner().and_then(|owner| load.get_mut(&owner)) {
+                *count += 1;
+            }
+        }
+
+        for deal in self._deals.iter_mut().filter(|deal| deal.stage().is_open() && deal.owner().is_none()) {
+            let lightest = load.iter().filter(|(_id, count)| **count < DEALS_PER_SALESPERSON).min_by_key(|(id, count)| (**count, **id)).map(|(id, _count)| *id);
+
+            match lightest {
+                Some(id) => {
+                    deal._owner = Some(id);
+                    deal._weeks_in_stage = 0;
+                    *load.get_mut(&id).unwrap() += 1;
+                },
+                None => break
+            }
+        }
+    }
+
+    // Commission is a month of the new contract value, doubled once the salesperson is past quota
+    //
+    fn book(&mut self, deal: &Deal) {
+        self._won += 1;
+
+        if let Some(owner) = deal.owner() {
+            let booked = self._booked_this_month.entry(owner).or_insert(0);
+
+            self._commission_due += if *booked >= MONTHLY_QUOTA { deal.value() * 2 } else { deal.value() };
+            *booked += deal.value();
+        }
+    }
+
+    pub fn take_commission(&mut self) -> u32 {
+        let commission = self._commission_due;
+        self._commission_due = 0;
+        commission
+    }
+
+    pub fn roll_month(&mut self) {
+        self._booked_this_month.clear();
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn deal_win_probability_test() {
+        let deal = Deal::new(1, Segment::MidMarket, 100);
+        let conditions = SalesConditions { feature_richness: 50, competition: 400, competitor_price: 90, customer_budget: 150 };
+
+        assert_eq!(deal.win_probability(&conditions, 60), 20 + 30 + 15 - 5 + 10 - 10);
+        assert_eq!(deal.value(), 800);
+    }
+
+    #[test]
+    fn pipeline_close_deal_test() {
+        let mut pipeline = Pipeline::new();
+        let conditions = SalesConditions { feature_richness: 100, competition: 0, competitor_price: 100, customer_budget: 100 };
+
+        // A zero rng always passes its rolls
+        let mut rng = StepRng::new(0, 0);
+        pipeline.generate_leads(1, &mut rng);
+        assert_eq!(pipeline.open_deals(), 1);
+        assert_eq!(pipeline.deals()[0].segment(), Segment::SMB);
+
+        let salespeople = vec![(7, 80)];
+        let mut won = Vec::new();
+        for _week in 0..10 {
+            won.extend(pipeline.update_week(&salespeople, &conditions, &mut rng));
+        }
+
+        assert_eq!(won.len(), 1);
+        assert_eq!(pipeline.open_deals(), 0);
+        assert_eq!(pipeline.booked_this_month(7), won[0].value());
+        assert_eq!(pipeline.take_commission(), won[0].value());
+        assert_eq!(pipeline.commission_due(), 0);
+    }
+
+    #[test]
+    fn pipeline_unworked_leads_test() {
+        let mut pipeline = Pipeline::new();
+        let conditions = SalesConditions { feature_richness: 100, competition: 0, competitor_price: 100, customer_budget: 100 };
+        let mut rng = StepRng::new(0, 0);
+
+        pipeline.generate_leads(3, &mut rng);
+        for _week in 0..5 {
+            pipeline.update_week(&[], &conditions, &mut rng);
+        }
+
+        assert_eq!(pipeline.open_deals(), 0);
+        assert_eq!(pipeline.lost(), 3);
+    }
+
+}
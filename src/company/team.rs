@@ -1,32 +1,235 @@
-use chrono::{DateTime};
-use chrono::{Local};
+use std::collections::HashMap;
+use std::fmt;
 
+use crate::employee::EmployeeType;
+use crate::employee::skills::{self, Skills, Work};
+use crate::world::timeframe::YearWeek;
 use super::Employee;
 
+// Productivity lost for each role an agile team is missing
+//
+const MISSING_ROLE_PENALTY: u16 = 15;
+
+// Teams thrown together without a way of working never quite gel
+//
+const AD_HOC_PRODUCTIVITY: u16 = 90;
+
+// Keeping in step with colleagues costs each person a little for everyone else in the
+// team - n people share n(n-1)/2 communication paths - and every new hire takes some
+// looking after. Beyond a point more people just add more talking.
+//
+const COORDINATION_COST: u16 = 4;
+const MENTORING_COST: u16 = 5;
+const MAX_COORDINATION_OVERHEAD: u16 = 80;
+
+// Each extra team working on the code has to fit in with the others, unless the
+// architecture lets them get on independently
+//
+const CROSS_TEAM_COST: u16 = 5;
+const MAX_CROSS_TEAM_OVERHEAD: u16 = 50;
+
+// How the team works
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum TeamType {
-    Agile,
-    Platform,
-    Etc
+    Agile,                          // cross-functional - wants a tester and somebody owning the product
+    Platform,                       // specialists - engineers and admins only
+    Etc                             // everything else, however it happened
+}
+
+impl TeamType {
+
+    pub fn next(&self) -> TeamType {
+        match self {
+            TeamType::Agile => TeamType::Platform,
+            TeamType::Platform => TeamType::Etc,
+            TeamType::Etc => TeamType::Agile
+        }
+    }
+}
+
+impl fmt::Display for TeamType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// What the team works on
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Mission {
+    Product,                        // new features
+    Platform,                       // keeping dependencies up to date
+    Operations                      // keeping customers happy and the lights on
+}
+
+impl Mission {
+
+    pub fn work(&self) -> Work {
+        match self {
+            Mission::Product => Work::Features,
+            Mission::Platform => Work::SecurityFixes,
+            Mission::Operations => Work::Operations
+        }
+    }
+
+    pub fn next(&self) -> Mission {
+        match self {
+            Mission::Product => Mission::Platform,
+            Mission::Platform => Mission::Operations,
+            Mission::Operations => Mission::Product
+        }
+    }
 }
 
+impl fmt::Display for Mission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
 
 pub struct Team {
     _name: String,
     _id: u16,
-    _members: Vec<Employee>,
-    _created: DateTime<Local>,
-    _updated: DateTime<Local>,
-    _productivity: u16   // 0 - 100
+    _team_type: TeamType,
+    _mission: Mission,
+    _members: Vec<u16>,             // IDs of employees in the company
+    _created: YearWeek,
+    _updated: YearWeek,             // last time the team was deliberately reorganised
+    _productivity: u16              // percentage - a team with just the right skills can pass 100
 }
 
 impl Team {
-    pub fn new(name: String, id: u16, members: Vec<Employee>, productivity: u16) -> Team {
-        Team { _name: name, _id: id, _members: members, _created: Local::now(), _updated: Local::now(), _productivity: productivity }
+    pub fn new(name: String, id: u16, team_type: TeamType, mission: Mission, created: YearWeek) -> Team {
+        Team { _name: name, _id: id, _team_type: team_type, _mission: mission, _members: Vec::new(), _created: created, _updated: created, _productivity: 0 }
+    }
+
+    pub fn name(&self) -> String {
+        self._name.to_string()
+    }
+
+    pub fn id(&self) -> u16 {
+        self._id
+    }
+
+    pub fn team_type(&self) -> TeamType {
+        self._team_type
+    }
+
+    pub fn mission(&self) -> Mission {
+        self._mission
+    }
+
+    pub fn members(&self) -> &Vec<u16> {
+        &self._members
+    }
+
+    pub fn created(&self) -> YearWeek {
+        self._created
+    }
+
+    pub fn updated(&self) -> YearWeek {
+        self._updated
+    }
+
+    pub fn productivity(&self) -> u16 {
+        self._productivity
     }
 
     pub fn get_size(&self) -> usize {
          self._members.len()
     }
+
+    pub fn has_member(&self, id: u16) -> bool {
+        self._members.contains(&id)
+    }
+
+    pub fn set_team_type(&mut self, team_type: TeamType, yearweek: YearWeek) {
+        self._team_type = team_type;
+        self._updated = yearweek;
+    }
+
+    pub fn set_mission(&mut self, mission: Mission, yearweek: YearWeek) {
+        self._mission = mission;
+        self._updated = yearweek;
+    }
+
+    pub fn add_member(&mut self, id: u16, yearweek: YearWeek) {
+        if !self.has_member(id) {
+            self._members.push(id);
+            self._updated = yearweek;
+        }
+    }
+
+    // People leave teams for all sorts of reasons so this isn't a reorganisation
+    //
+    pub fn remove_member(&mut self, id: u16) -> bool {
+        let before = self._members.len();
+        self._members.retain(|member| *member != id);
+        self._members.len() != before
+    }
+
+    // Productivity comes from how good the members are and whether the team has the mix
+    // its way of working needs
+    //
+    pub fn recalculate_productivity(&mut self, employees: &HashMap<u16, Employee>) {
+        let members: Vec<&Employee> = self._members.iter().filter_map(|id| employees.get(id)).collect();
+        self._productivity = productivity(self._team_type, self._mission, &members);
+    }
+}
+
+// Skills the team has for its mission (0 - 100)
+//
+pub fn skill_fit(mission: Mission, members: &[&Employee]) -> u16 {
+    let skills: Vec<&Skills> = members.iter().map(|member| member.skills()).collect();
+    skills::fit(mission.work(), &skills)
+}
+
+// Productivity (percentage) of a group of people working a particular way on a mission.
+// It comes from how good they are, whether they have the mix of roles their way of working
+// needs and whether they have the skills the work does.
+//
+pub fn productivity(team_type: TeamType, mission: Mission, members: &[&Employee]) -> u16 {
+    if members.is_empty() {
+        return 0
+    }
+
+    let efficiency = u32::min(100, members.iter().map(|member| member.efficiency() as u32).sum::<u32>() / members.len() as u32) as u16;
+    let has = |roles: &[EmployeeType]| members.iter().any(|member| roles.contains(&member.employee_type()));
+
+    let balance = match team_type {
+        TeamType::Agile => {
+            let missing = [!has(&[EmployeeType::Tester]), !has(&[EmployeeType::ProductOwner, EmployeeType::ProductManager, EmployeeType::CPO])];
+            100 - MISSING_ROLE_PENALTY * missing.iter().filter(|missing| **missing).count() as u16
+        },
+        TeamType::Platform => {
+            let specialists = [EmployeeType::Developer, EmployeeType::Administrator, EmployeeType::CTO];
+            match members.iter().all(|member| specialists.contains(&member.employee_type())) {
+                true => 100,
+                false => 100 - MISSING_ROLE_PENALTY
+            }
+        },
+        TeamType::Etc => AD_HOC_PRODUCTIVITY
+    };
+
+    let fit_factor = skills::fit_factor(skill_fit(mission, members));
+
+    return ( efficiency as u32 * balance as u32 / 100 * fit_factor as u32 / 100 ) as u16
+}
+
+// Percentage of everyone's time lost to coordination in a team of this size with this
+// many people onboarding
+//
+pub fn coordination_overhead(size: usize, onboarding: usize) -> u16 {
+    let overhead = size.saturating_sub(1) * COORDINATION_COST as usize + onboarding * MENTORING_COST as usize;
+    usize::min(MAX_COORDINATION_OVERHEAD as usize, overhead) as u16
+}
+
+// Percentage lost by every team to working alongside the others
+//
+pub fn cross_team_overhead(teams: usize, independence: u16) -> u16 {
+    let overhead = teams.saturating_sub(1) as u32 * CROSS_TEAM_COST as u32 * ( 100 - u16::min(100, independence) ) as u32 / 100;
+    u32::min(MAX_CROSS_TEAM_OVERHEAD as u32, overhead) as u16
 }
 
 #[cfg(test)]
@@ -39,12 +242,59 @@ mod test {
     #[test]
     fn team_test() {
 
-        let employee = Employee::new(EmployeeType::Developer, 1, "Richard".to_string(),  1, 35, 23, 35);
-        let mut employee_list = Vec::new();
-        employee_list.push(employee);
-        let team = Team::new("Thundercats".to_string(), 0, employee_list, 50);
+        let mut employee = Employee::new(EmployeeType::Developer, "Richard".to_string(),  1, 35, 23, 35);
+        employee.set_id(7);
+        let mut employees = HashMap::new();
+        employees.insert(employee.id(), employee);
+
+        let yearweek = YearWeek::new(2000, 1).unwrap();
+        let mut team = Team::new("Thundercats".to_string(), 0, TeamType::Agile, Mission::Product, yearweek);
+        team.add_member(7, yearweek);
+        team.add_member(7, yearweek);
         assert_eq!(team.get_size(), 1);
- 
+
+        // No tester and no product owner, and a typical developer's skills just short of what the work needs
+        team.recalculate_productivity(&employees);
+        assert_eq!(team.productivity(), 23 * 70 / 100 * 99 / 100);
+
+        assert!(team.remove_member(7));
+        assert!(!team.remove_member(7));
     }
- 
-} 
\ No newline at end of file
+
+    #[test]
+    fn team_productivity_test() {
+
+        let developer = Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85);
+        let tester = Employee::new(EmployeeType::Tester, "Tester 1".to_string(), 28, 50, 90, 70);
+        let owner = Employee::new(EmployeeType::ProductOwner, "Product Owner 1".to_string(), 35, 70, 95, 80);
+
+        assert_eq!(productivity(TeamType::Agile, Mission::Product, &[]), 0);
+        assert_eq!(productivity(TeamType::Agile, Mission::Product, &[&developer, &tester, &owner]), 95 * 109 / 100);
+        assert_eq!(productivity(TeamType::Platform, Mission::Product, &[&developer]), 99);
+        assert_eq!(productivity(TeamType::Platform, Mission::Product, &[&developer, &tester]), 95 * 85 / 100 * 105 / 100);
+        assert_eq!(productivity(TeamType::Etc, Mission::Product, &[&developer]), AD_HOC_PRODUCTIVITY * 99 / 100);
+
+        // The same people are a lot less use keeping the lights on
+        assert_eq!(skill_fit(Mission::Operations, &[&developer]), 42);
+        assert_eq!(productivity(TeamType::Platform, Mission::Operations, &[&developer]), 87);
+    }
+
+    #[test]
+    fn coordination_overhead_test() {
+
+        assert_eq!(coordination_overhead(1, 0), 0);
+        assert_eq!(coordination_overhead(5, 0), 16);
+        assert_eq!(coordination_overhead(5, 2), 26);
+        assert_eq!(coordination_overhead(40, 0), MAX_COORDINATION_OVERHEAD);
+
+        // Adding people past a point gets less done
+        let output = |size: usize| size * ( 100 - coordination_overhead(size, 0) as usize );
+        assert!(output(12) > output(6));
+        assert!(output(20) < output(12));
+
+        assert_eq!(cross_team_overhead(1, 0), 0);
+        assert_eq!(cross_team_overhead(3, 10), 9);
+        assert_eq!(cross_team_overhead(3, 90), 1);
+    }
+
+}
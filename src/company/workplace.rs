@@ -0,0 +1,178 @@
+use serde::Deserialize;
+use std::fmt;
+
+// Every company starts out in one office here
+//
+pub const HEADQUARTERS: Region = Region::London;
+
+// Fitting out a new office before anybody can sit in it
+//
+pub const OFFICE_FIT_OUT: u32 = 20;
+
+// Rent on an office with nobody in it, a year
+//
+const OFFICE_BASE_LEASE: u32 = 12;
+
+// Working across sites costs every extra office some collaboration - unless everybody is
+// remote already - but there's only so bad it gets
+//
+const SPLIT_SITE_COLLABORATION: u16 = 5;
+const MIN_COLLABORATION: u16 = 60;
+
+// Where the company expects people to do their work
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Deserialize)]
+pub enum Policy {
+    Office,
+    Hybrid,
+    Remote
+}
+
+impl Policy {
+
+    pub fn next(&self) -> Policy {
+        match self {
+            Policy::Office => Policy::Hybrid,
+            Policy::Hybrid => Policy::Remote,
+            Policy::Remote => Policy::Office
+        }
+    }
+
+    // Percentage of people who need a desk of their own
+    //
+    pub fn desk_share(&self) -> u32 {
+        match self {
+            Policy::Office => 100,
+            Policy::Hybrid => 60,
+            Policy::Remote => 0
+        }
+    }
+
+    // How much of the job market will consider us (percentage) - fewer people want to
+    // commute every day
+    //
+    pub fn hiring_reach(&self) -> u16 {
+        match self {
+            Policy::Office => 100,
+            Policy::Hybrid => 125,
+            Policy::Remote => 160
+        }
+    }
+
+    // How people feel about it compared with five days a week in the office
+    //
+    pub fn morale(&self) -> i16 {
+        match self {
+            Policy::Office => 0,
+            Policy::Hybrid => 8,
+            Policy::Remote => 5
+        }
+    }
+
+    // How well people work together (percentage)
+    //
+    pub fn collaboration(&self) -> u16 {
+        match self {
+            Policy::Office => 100,
+            Policy::Hybrid => 95,
+            Policy::Remote => 85
+        }
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// Where we can open an office, or hire somebody who works from home
+//
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize)]
+pub enum Region {
+    London,
+    Berlin,
+    Lisbon,
+    Austin,
+    Bangalore
+}
+
+impl Region {
+
+    pub fn all() -> [Region; 5] {
+        [Region::London, Region::Berlin, Region::Lisbon, Region::Austin, Region::Bangalore]
+    }
+
+    // Local pay against what people earn at headquarters (percentage)
+    //
+    pub fn salary(&self) -> u32 {
+        match self {
+            Region::London => 100,
+            Region::Berlin => 90,
+            Region::Lisbon => 65,
+            Region::Austin => 115,
+            Region::Bangalore => 40
+        }
+    }
+
+    // Rent on a desk for a year
+    //
+    pub fn desk_lease(&self) -> u32 {
+        match self {
+            Region::London => 12,
+            Region::Berlin => 8,
+            Region::Lisbon => 6,
+            Region::Austin => 9,
+            Region::Bangalore => 3
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// What an office costs a month with enough desks for this many people
+//
+pub fn office_lease(region: Region, people: u32, policy: Policy) -> u32 {
+    let desks = ( people * policy.desk_share() ).div_ceil(100);
+    ( OFFICE_BASE_LEASE + desks * region.desk_lease() ) / 12
+}
+
+// How well people work together when they're spread over this many sites (percentage)
+//
+pub fn collaboration(policy: Policy, sites: usize) -> u16 {
+    let split = match policy {
+        Policy::Remote => 0,
+        _ => SPLIT_SITE_COLLABORATION * sites.saturating_sub(1) as u16
+    };
+    u16::max(MIN_COLLABORATION, policy.collaboration().saturating_sub(split))
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn office_lease_test() {
+        assert_eq!(office_lease(Region::London, 0, Policy::Office), 1);
+        assert_eq!(office_lease(Region::London, 10, Policy::Office), 11);
+        assert_eq!(office_lease(Region::London, 10, Policy::Hybrid), 7);
+        assert_eq!(office_lease(Region::Bangalore, 10, Policy::Office), 3);
+        assert_eq!(office_lease(Region::London, 10, Policy::Remote), 1);
+    }
+
+    #[test]
+    fn collaboration_test() {
+        assert_eq!(collaboration(Policy::Office, 1), 100);
+        assert_eq!(collaboration(Policy::Office, 3), 90);
+        assert_eq!(collaboration(Policy::Hybrid, 10), MIN_COLLABORATION);
+        assert_eq!(collaboration(Policy::Remote, 4), 85);
+        assert_eq!(Policy::Remote.next(), Policy::Office);
+    }
+
+}
@@ -1,3 +1,27 @@
+use serde::Deserialize;
+use std::fmt;
+
+use crate::world::timeframe::YearWeek;
+use crate::company::workplace::{self, Region};
+
+pub mod skills;
+pub mod career;
+pub mod names;
+pub mod contract;
+
+use skills::{Skill, Skills};
+use career::{Level, Review};
+use contract::Contract;
+
+// Learning points that make up one point of efficiency
+//
+const LEARNING_PER_EFFICIENCY: u16 = 100;
+
+// Weeks a new hire takes to get fully up to speed, and how much they manage from day one
+//
+pub const ONBOARDING_WEEKS: u16 = 12;
+const FIRST_WEEK_CONTRIBUTION: u16 = 25;
+
 // Structure
 //
 //                            CEO
@@ -9,7 +33,7 @@
 //  Administrator 
 //
 //
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Deserialize)]
 pub enum EmployeeType {
     Developer,
     Tester,
@@ -26,21 +50,65 @@ pub enum EmployeeType {
     ProductManager
 }
 
+impl EmployeeType {
+
+    pub fn all() -> [EmployeeType; 13] {
+        [EmployeeType::CEO, EmployeeType::CTO, EmployeeType::FinanceDirector, EmployeeType::CPO, EmployeeType::CMO,
+         EmployeeType::Developer, EmployeeType::Tester, EmployeeType::Administrator, EmployeeType::Accountant, EmployeeType::Salesperson,
+         EmployeeType::ProductOwner, EmployeeType::ProductManager, EmployeeType::Marketeer]
+    }
+}
+
+// Where a gain in skill came from
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SkillSource {
+    Experience,
+    Mentoring,
+    Training
+}
+
+impl fmt::Display for SkillSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct SkillChange {
+    pub yearweek: YearWeek,
+    pub efficiency: u16,            // after the change
+    pub source: SkillSource
+}
+
 
 pub struct Employee {
      _employee_type: EmployeeType,
-     _id: u16,
+     _id: u16,                      // given out by the company when they join - 0 until then
      _name: String,
      _age: u16,
      _compensation: u32,
      _efficiency: u16,
-     _talent: u16
+     _talent: u16,
+     _morale: u16,                  // 0 - 100 - how they feel about working here
+     _manager: Option<u16>,         // ID of who they report to
+     _onboarding: u16,              // weeks until they're up to speed
+     _learning: u16,                // progress towards the next point of efficiency
+     _skill_history: Vec<SkillChange>,
+     _skills: Skills,               // what they're good at
+     _level: Level,
+     _equity: u32,                  // share options granted over the years
+     _reviews: Vec<Review>,
+     _skipped_reviews: u16,         // reviews in a row they didn't get
+     _contract: Option<Contract>,   // contractors are only here for a fixed term
+     _region: Region                // where they're based
 }
 
 impl Employee {
 
-    pub fn new(employee_type :EmployeeType, id :u16, name :String, age :u16, compensation :u32, efficiency :u16, talent :u16) -> Employee {
-        Employee { _employee_type: employee_type, _id: id, _name: name, _age: age, _compensation: compensation, _efficiency: efficiency, _talent: talent }
+    pub fn new(employee_type :EmployeeType, name :String, age :u16, compensation :u32, efficiency :u16, talent :u16) -> Employee {
+        Employee { _employee_type: employee_type, _id: 0, _name: name, _age: age, _compensation: compensation, _efficiency: efficiency, _talent: talent, _morale: 70, _manager: None, _onboarding: 0, _learning: 0, _skill_history: Vec::new(), _skills: Skills::typical(employee_type),
+                   _level: Level::for_efficiency(efficiency), _equity: 0, _reviews: Vec::new(), _skipped_reviews: 0, _contract: None, _region: workplace::HEADQUARTERS }
     }  
 
     pub fn employee_type(&self) -> EmployeeType {
@@ -51,6 +119,10 @@ impl Employee {
         self._id
     }
 
+    pub fn set_id(&mut self, id :u16) {
+        self._id = id
+    }
+
     pub fn name(&self) -> String {
         self._name.to_string()
     }
@@ -59,6 +131,10 @@ impl Employee {
         self._age
     }
 
+    pub fn compensation(&self) -> u32 {
+        self._compensation
+    }
+
     pub fn salary(&self) -> u32 {
         (self._compensation as f32 / 12f32) as u32
     }
@@ -71,6 +147,99 @@ impl Employee {
         self._talent
     }
 
+    pub fn morale(&self) -> u16 {
+        self._morale
+    }
+
+    pub fn adjust_morale(&mut self, change :i16) {
+        self._morale = i16::clamp(self._morale as i16 + change, 0, 100) as u16
+    }
+
+    pub fn manager(&self) -> Option<u16> {
+        self._manager
+    }
+
+    pub fn set_manager(&mut self, manager :Option<u16>) {
+        self._manager = manager
+    }
+
+    pub fn onboarding(&self) -> u16 {
+        self._onboarding
+    }
+
+    pub fn is_onboarding(&self) -> bool {
+        self._onboarding > 0
+    }
+
+    pub fn start_onboarding(&mut self) {
+        self._onboarding = ONBOARDING_WEEKS
+    }
+
+    // Some people need less time to find their feet than others
+    //
+    pub fn start_onboarding_for(&mut self, weeks :u16) {
+        self._onboarding = u16::min(weeks, ONBOARDING_WEEKS)
+    }
+
+    pub fn progress_onboarding(&mut self) {
+        self._onboarding = self._onboarding.saturating_sub(1)
+    }
+
+    // Percentage of a full contribution - rises steadily while they find their feet
+    //
+    pub fn ramp_up(&self) -> u16 {
+        100 - ( 100 - FIRST_WEEK_CONTRIBUTION ) * self._onboarding / ONBOARDING_WEEKS
+    }
+
+    pub fn skills(&self) -> &Skills {
+        &self._skills
+    }
+
+    pub fn set_skills(&mut self, skills :Skills) {
+        self._skills = skills
+    }
+
+    pub fn improve_skill(&mut self, skill :Skill, points :u16) {
+        self._skills.improve(skill, points)
+    }
+
+    // Somebody who's particularly strong in one area for their role
+    //
+    pub fn specialise(&mut self, skill :Skill) {
+        self._skills.improve(skill, skills::SPECIALISM_BONUS)
+    }
+
+    // How good they can get - the more talented go further
+    //
+    pub fn skill_ceiling(&self) -> u16 {
+        100 + self._talent / 4
+    }
+
+    pub fn skill_history(&self) -> &Vec<SkillChange> {
+        &self._skill_history
+    }
+
+    // Put learning towards their efficiency - returns the points of efficiency gained
+    //
+    pub fn learn(&mut self, points :u16, source :SkillSource, yearweek :YearWeek) -> u16 {
+        if self._efficiency >= self.skill_ceiling() {
+            self._learning = 0;
+            return 0
+        }
+
+        self._learning += points;
+        let gained = u16::min(self._learning / LEARNING_PER_EFFICIENCY, self.skill_ceiling() - self._efficiency);
+        self._learning %= LEARNING_PER_EFFICIENCY;
+
+        if gained > 0 {
+            self.add_efficiency(gained);
+            self._skill_history.push(SkillChange { yearweek, efficiency: self._efficiency, source });
+        }
+
+        return gained
+    }
+
+    #[allow(dead_code)]
     pub fn add_talent(&mut self, talent :u16) {
         self._talent += talent
     }
@@ -79,6 +248,7 @@ impl Employee {
         self._efficiency += efficiency
     }
 
+    #[allow(dead_code)]
     pub fn remove_efficiency(&mut self, efficiency :u16) {
         self._efficiency -= efficiency
     }
@@ -87,10 +257,12 @@ impl Employee {
         self._compensation += compensation
     }
 
+    #[allow(dead_code)]
     pub fn remove_compensation(&mut self, compensation :u32) {
         self._compensation -= compensation
     }
 
+    #[allow(dead_code)]
     pub fn add_age(&mut self, age :u16) {
         self._age += age
     }
@@ -99,6 +271,64 @@ impl Employee {
         self._employee_type = employee_type
     }
 
+    pub fn level(&self) -> Level {
+        self._level
+    }
+
+    pub fn equity(&self) -> u32 {
+        self._equity
+    }
+
+    pub fn reviews(&self) -> &Vec<Review> {
+        &self._reviews
+    }
+
+    pub fn skipped_reviews(&self) -> u16 {
+        self._skipped_reviews
+    }
+
+    // Sit down for the annual review and act on it - the bonus is left for payroll
+    //
+    pub fn review(&mut self, yearweek :YearWeek) -> Review {
+        let review = Review::new(yearweek, self._level, self._compensation, self._efficiency, self._morale);
+
+        self.add_compensation(review.raise);
+        self.adjust_morale(review.morale());
+        self._equity += review.equity;
+        self._level = review.promoted.unwrap_or(self._level);
+        self._skipped_reviews = 0;
+        self._reviews.push(review);
+
+        return review
+    }
+
+    // Nobody likes being forgotten, and it gets worse every year it happens
+    //
+    pub fn skip_review(&mut self) {
+        self._skipped_reviews += 1;
+        self.adjust_morale(career::SKIPPED_REVIEW_MORALE * self._skipped_reviews as i16);
+    }
+
+    pub fn contract(&self) -> Option<Contract> {
+        self._contract
+    }
+
+    pub fn is_contractor(&self) -> bool {
+        self._contract.is_some()
+    }
+
+    pub fn set_contract(&mut self, contract :Option<Contract>) {
+        self._contract = contract
+    }
+
+    pub fn region(&self) -> Region {
+        self._region
+    }
+
+    pub fn set_region(&mut self, region :Region) {
+        self._region = region
+    }
+
 }
 
 
@@ -110,14 +340,14 @@ mod test {
 
     #[test]
     fn employee_age_test() {
-        let mut employee = Employee::new(EmployeeType::Developer, 1, "Developer 2".to_string(), 23, 35, 89, 77);
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
         employee.add_age(11);
         assert_eq!(employee._age, 34);
     }
 
     #[test]
     fn employee_efficiency_tests() {
-        let mut employee = Employee::new(EmployeeType::Developer, 2, "Developer 2".to_string(), 23, 35, 89, 77);
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
         
         employee.add_efficiency(11);
         assert_eq!(employee._efficiency, 100);
@@ -126,9 +356,51 @@ mod test {
         assert_eq!(employee._efficiency, 89);
     }
 
+    #[test]
+    fn employee_morale_test() {
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
+        assert_eq!(employee.morale(), 70);
+
+        employee.adjust_morale(50);
+        assert_eq!(employee.morale(), 100);
+
+        employee.adjust_morale(-120);
+        assert_eq!(employee.morale(), 0);
+    }
+
+    #[test]
+    fn employee_onboarding_test() {
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
+        assert_eq!(employee.ramp_up(), 100);
+
+        employee.start_onboarding();
+        assert_eq!(employee.ramp_up(), FIRST_WEEK_CONTRIBUTION);
+
+        for _week in 0..ONBOARDING_WEEKS / 2 {
+            employee.progress_onboarding();
+        }
+        assert_eq!(employee.ramp_up(), 63);
+    }
+
+    #[test]
+    fn employee_learning_test() {
+        let yearweek = YearWeek::new(2000, 1).unwrap();
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 80);
+        assert_eq!(employee.skill_ceiling(), 120);
+
+        assert_eq!(employee.learn(60, SkillSource::Experience, yearweek), 0);
+        assert_eq!(employee.learn(60, SkillSource::Mentoring, yearweek), 1);
+        assert_eq!(employee.skill_history(), &vec![SkillChange { yearweek, efficiency: 90, source: SkillSource::Mentoring }]);
+
+        // Nobody gets better than their talent allows
+        assert_eq!(employee.learn(5000, SkillSource::Training, yearweek), 30);
+        assert_eq!(employee.learn(500, SkillSource::Training, yearweek), 0);
+        assert_eq!(employee.efficiency(), 120);
+    }
+
     #[test]
     fn employee_compensation_test() {
-        let mut employee = Employee::new(EmployeeType::Developer, 3, "Developer 2".to_string(), 23, 35, 89, 77);
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
         employee.add_compensation(10);
         assert_eq!(employee._compensation, 45);
 
@@ -138,22 +410,54 @@ mod test {
 
     #[test]
     fn employee_type_test() {
-        let mut employee = Employee::new(EmployeeType::Developer, 4, "Developer 2".to_string(), 23, 35, 89, 77);
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
         assert_eq!(employee._employee_type, EmployeeType::Developer);
 
         employee.set_employee_type(EmployeeType::Administrator);
         assert_eq!(employee._employee_type, EmployeeType::Administrator);
     }
 
+    #[test]
+    fn employee_review_test() {
+        let yearweek = YearWeek::new(2000, 52).unwrap();
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 100, 106, 77);
+        assert_eq!(employee.level(), Level::Staff);
+
+        // Two years forgotten costs 8 then 16
+        employee.skip_review();
+        employee.skip_review();
+        assert_eq!(employee.morale(), 70 - 24);
+
+        let review = employee.review(yearweek);
+        assert_eq!(review.rating, career::Rating::Meets);
+        assert_eq!(employee.compensation(), 103);
+        assert_eq!(employee.skipped_reviews(), 0);
+        assert_eq!(employee.reviews().len(), 1);
+    }
+
+    #[test]
+    fn employee_contract_test() {
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
+        assert!(!employee.is_contractor());
+
+        employee.set_contract(Some(Contract::new(contract::Supplier::Agency, YearWeek::new(2000, 1).unwrap())));
+        employee.start_onboarding_for(contract::Supplier::Agency.onboarding_weeks());
+        assert!(employee.is_contractor());
+        assert_eq!(employee.ramp_up(), 94);
+    }
+
     #[test]
     fn employee_id_test() {
-        let employee = Employee::new(EmployeeType::Developer, 5, "Developer 2".to_string(), 23, 35, 89, 77);
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
+        assert_eq!(employee.id(), 0);
+
+        employee.set_id(5);
         assert_eq!(employee.id(), 5);
     }
 
     #[test]
     fn employee_talent_test() {
-        let mut employee = Employee::new(EmployeeType::Developer, 5, "Developer 2".to_string(), 23, 35, 89, 77);
+        let mut employee = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 89, 77);
         employee.add_talent(10);
         assert_eq!(employee.talent(), 87);
     }
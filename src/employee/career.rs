@@ -0,0 +1,227 @@
+use std::fmt;
+
+use crate::world::timeframe::YearWeek;
+
+// Pay rise (percentage), bonus (percentage of a year's compensation) and morale change
+// that come with each rating
+//
+const EXCEEDS: (u16, u16, i16) = (10, 20, 5);
+const MEETS: (u16, u16, i16) = (3, 5, 2);
+const BELOW: (u16, u16, i16) = (0, 0, -5);
+
+// A promotion comes with a bigger rise and a lift
+//
+const PROMOTION_RAISE: u16 = 10;
+const PROMOTION_MORALE: i16 = 10;
+
+// Nobody performs at their best when they're miserable
+//
+const UNHAPPY_MORALE: u16 = 30;
+
+// Each review in a row that doesn't happen hurts more than the last
+//
+pub const SKIPPED_REVIEW_MORALE: i16 = -8;
+
+// Share options are each a thousandth of the company - what's granted comes out of
+// everybody else's share
+//
+pub const OPTION_POOL: u32 = 1000;
+
+// Where somebody is on the ladder - the same levels whatever the role
+//
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Level {
+    Junior,
+    Mid,
+    Senior,
+    Staff,
+    Principal
+}
+
+impl Level {
+
+    pub fn next(&self) -> Option<Level> {
+        match self {
+            Level::Junior => Some(Level::Mid),
+            Level::Mid => Some(Level::Senior),
+            Level::Senior => Some(Level::Staff),
+            Level::Staff => Some(Level::Principal),
+            Level::Principal => None
+        }
+    }
+
+    // The efficiency we expect from somebody at this level
+    //
+    pub fn expected_efficiency(&self) -> u16 {
+        match self {
+            Level::Junior => 0,
+            Level::Mid => 80,
+            Level::Senior => 95,
+            Level::Staff => 105,
+            Level::Principal => 115
+        }
+    }
+
+    // The level somebody this good would be hired at
+    //
+    pub fn for_efficiency(efficiency: u16) -> Level {
+        let mut level = Level::Junior;
+        while let Some(next) = level.next().filter(|next| efficiency >= next.expected_efficiency()) {
+            level = next;
+        }
+        level
+    }
+
+    // Share options handed out with a strong review - the senior people are the ones to keep
+    //
+    pub fn equity_grant(&self) -> u32 {
+        match self {
+            Level::Junior | Level::Mid => 0,
+            Level::Senior => 5,
+            Level::Staff => 10,
+            Level::Principal => 20
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Rating {
+    Exceeds,                        // working at the next level up
+    Meets,
+    Below
+}
+
+impl Rating {
+
+    // Measured against what their level expects. Unhappy people drop a rating.
+    //
+    pub fn of(level: Level, efficiency: u16, morale: u16) -> Rating {
+        let rating = if level.next().is_some_and(|next| efficiency >= next.expected_efficiency()) {
+            Rating::Exceeds
+        } else if efficiency >= level.expected_efficiency() {
+            Rating::Meets
+        } else {
+            Rating::Below
+        };
+
+        match (rating, morale < UNHAPPY_MORALE) {
+            (Rating::Exceeds, true) => Rating::Meets,
+            (Rating::Meets, true) => Rating::Below,
+            (rating, _) => rating
+        }
+    }
+
+    fn outcome(&self) -> (u16, u16, i16) {
+        match self {
+            Rating::Exceeds => EXCEEDS,
+            Rating::Meets => MEETS,
+            Rating::Below => BELOW
+        }
+    }
+
+    pub fn raise(&self) -> u16 {
+        self.outcome().0
+    }
+
+    pub fn bonus(&self) -> u16 {
+        self.outcome().1
+    }
+
+    pub fn morale(&self) -> i16 {
+        self.outcome().2
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rating::Exceeds => write!(f, "Exceeds expectations"),
+            Rating::Meets => write!(f, "Meets expectations"),
+            Rating::Below => write!(f, "Below expectations")
+        }
+    }
+}
+
+// What came out of somebody's annual review
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Review {
+    pub yearweek: YearWeek,
+    pub rating: Rating,
+    pub raise: u32,                 // added to their compensation
+    pub bonus: u32,                 // paid once with the next payroll
+    pub equity: u32,                // share options granted
+    pub promoted: Option<Level>
+}
+
+impl Review {
+
+    // Work out the review for somebody on this level and pay
+    //
+    pub fn new(yearweek: YearWeek, level: Level, compensation: u32, efficiency: u16, morale: u16) -> Review {
+        let rating = Rating::of(level, efficiency, morale);
+        let promoted = match rating {
+            Rating::Exceeds => level.next(),
+            _ => None
+        };
+
+        let raise_percentage = rating.raise() + if promoted.is_some() { PROMOTION_RAISE } else { 0 };
+        let equity = match rating {
+            Rating::Exceeds => promoted.unwrap_or(level).equity_grant(),
+            _ => 0
+        };
+
+        Review { yearweek,
+                 rating,
+                 raise: compensation * raise_percentage as u32 / 100,
+                 bonus: compensation * rating.bonus() as u32 / 100,
+                 equity,
+                 promoted }
+    }
+
+    pub fn morale(&self) -> i16 {
+        self.rating.morale() + if self.promoted.is_some() { PROMOTION_MORALE } else { 0 }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn level_test() {
+        assert_eq!(Level::for_efficiency(60), Level::Junior);
+        assert_eq!(Level::for_efficiency(100), Level::Senior);
+        assert_eq!(Level::for_efficiency(125), Level::Principal);
+        assert_eq!(Level::Principal.next(), None);
+        assert!(Level::Staff > Level::Senior);
+    }
+
+    #[test]
+    fn review_test() {
+        let yearweek = YearWeek::new(2000, 52).unwrap();
+
+        let review = Review::new(yearweek, Level::Senior, 100, 100, 70);
+        assert_eq!(review.rating, Rating::Meets);
+        assert_eq!((review.raise, review.bonus, review.equity, review.promoted), (3, 5, 0, None));
+
+        // Working at the next level up gets a promotion, and options to go with it
+        let review = Review::new(yearweek, Level::Senior, 100, 106, 70);
+        assert_eq!(review.rating, Rating::Exceeds);
+        assert_eq!((review.raise, review.bonus, review.equity, review.promoted), (20, 20, 10, Some(Level::Staff)));
+        assert_eq!(review.morale(), 15);
+
+        // The same work from somebody miserable
+        assert_eq!(Review::new(yearweek, Level::Senior, 100, 106, 20).rating, Rating::Meets);
+        assert_eq!(Review::new(yearweek, Level::Senior, 100, 90, 70).morale(), -5);
+    }
+
+}
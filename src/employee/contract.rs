@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::world::timeframe::YearWeek;
+
+// Contracts run a quarter at a time
+//
+pub const CONTRACT_TERM_WEEKS: u16 = 13;
+
+// Where somebody who isn't on the payroll comes from
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Supplier {
+    Freelance,                      // one person working for themselves
+    Agency                          // outsourced to whoever the agency has on the bench
+}
+
+impl Supplier {
+
+    // What they cost against a permanent hire doing the same job (percentage)
+    //
+    pub fn rate(&self) -> u32 {
+        match self {
+            Supplier::Freelance => 160,
+            Supplier::Agency => 200
+        }
+    }
+
+    // Weeks before they're up to speed - they've done this before
+    //
+    pub fn onboarding_weeks(&self) -> u16 {
+        match self {
+            Supplier::Freelance => 3,
+            Supplier::Agency => 1
+        }
+    }
+
+    // Corners cut each week they spend writing code - they won't be here to live with them
+    //
+    pub fn shortcuts(&self) -> u16 {
+        match self {
+            Supplier::Freelance => 2,
+            Supplier::Agency => 4
+        }
+    }
+}
+
+impl fmt::Display for Supplier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// A fixed term of work for somebody who isn't on the payroll
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Contract {
+    pub supplier: Supplier,
+    pub ends: YearWeek              // the first week they're no longer here
+}
+
+impl Contract {
+
+    pub fn new(supplier: Supplier, starts: YearWeek) -> Contract {
+        Contract { supplier, ends: starts.add_weeks(CONTRACT_TERM_WEEKS as u32) }
+    }
+
+    pub fn has_ended(&self, yearweek: YearWeek) -> bool {
+        yearweek >= self.ends
+    }
+
+    pub fn weeks_left(&self, yearweek: YearWeek) -> u32 {
+        match self.has_ended(yearweek) {
+            true => 0,
+            false => self.ends.difference_weeks(&yearweek)
+        }
+    }
+
+    // Another term on the end of this one
+    //
+    pub fn extend(&mut self) {
+        self.ends = self.ends.add_weeks(CONTRACT_TERM_WEEKS as u32)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn contract_term_test() {
+        let starts = YearWeek::new(2000, 50).unwrap();
+        let mut contract = Contract::new(Supplier::Agency, starts);
+        assert_eq!(contract.ends, YearWeek::new(2001, 11).unwrap());
+        assert_eq!(contract.weeks_left(starts), 13);
+        assert!(!contract.has_ended(YearWeek::new(2001, 10).unwrap()));
+        assert!(contract.has_ended(contract.ends));
+        assert_eq!(contract.weeks_left(YearWeek::new(2001, 20).unwrap()), 0);
+
+        contract.extend();
+        assert_eq!(contract.weeks_left(starts), 26);
+    }
+
+}
@@ -0,0 +1,97 @@
+use rand::Rng;
+use serde::Deserialize;
+
+const FIRST_NAMES: [&str; 40] = [
+    "Ada", "Alan", "Amara", "Anil", "Beatriz", "Bjorn", "Carmen", "Chen", "Dmitri", "Elena",
+    "Emeka", "Farah", "Gareth", "Grace", "Hana", "Ivan", "Jamal", "Julia", "Kenji", "Leila",
+    "Liam", "Maria", "Mateo", "Mei", "Nadia", "Nikhil", "Olga", "Omar", "Priya", "Rafael",
+    "Rosa", "Sami", "Sofia", "Tariq", "Tomas", "Uma", "Viktor", "Wen", "Yusuf", "Zara"
+];
+
+const LAST_NAMES: [&str; 40] = [
+    "Abara", "Andersen", "Bauer", "Bianchi", "Costa", "Da Silva", "Dubois", "Eriksson", "Fischer", "Garcia",
+    "Gupta", "Haddad", "Hughes", "Ivanova", "Jensen", "Kaur", "Kim", "Kowalski", "Lopez", "Mensah",
+    "Moreau", "Nakamura", "Nguyen", "Novak", "Okafor", "O'Brien", "Patel", "Petrov", "Quinn", "Rossi",
+    "Sato", "Schmidt", "Singh", "Tanaka", "Torres", "Van Dijk", "Wang", "Williams", "Yilmaz", "Zhang"
+];
+
+// Tries at a random name before working through them in order
+//
+const RANDOM_TRIES: usize = 20;
+
+// Where the names of new hires come from - a scenario can bring its own
+//
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamePool {
+    pub first: Vec<String>,
+    pub last: Vec<String>
+}
+
+impl Default for NamePool {
+    fn default() -> NamePool {
+        NamePool { first: FIRST_NAMES.iter().map(|name| name.to_string()).collect(),
+                   last: LAST_NAMES.iter().map(|name| name.to_string()).collect() }
+    }
+}
+
+impl NamePool {
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.first.is_empty() || self.last.is_empty() {
+            return Err("A name pool needs at least one first name and one last name".to_string())
+        }
+        return Ok(())
+    }
+
+    fn full_name(&self, index: usize) -> String {
+        format!("{} {}", self.first[index % self.first.len()], self.last[index / self.first.len()])
+    }
+
+    // A name nobody here already has. Once every combination is taken people get told
+    // apart by number.
+    //
+    pub fn generate<R: Rng>(&self, rng: &mut R, taken: &[String]) -> String {
+        let combinations = self.first.len() * self.last.len();
+        if combinations == 0 {
+            return format!("Employee {}", taken.len() + 1)
+        }
+
+        let free = |name: &String| !taken.contains(name);
+        let random = (0..RANDOM_TRIES).map(|_| self.full_name(rng.gen_range(0, combinations))).find(free);
+        if let Some(name) = random.or_else(|| (0..combinations).map(|index| self.full_name(index)).find(free)) {
+            return name
+        }
+
+        let name = self.full_name(rng.gen_range(0, combinations));
+        let number = (2..).find(|number| free(&format!("{} {}", name, number))).unwrap_or(2);
+        return format!("{} {}", name, number)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn name_pool_test() {
+        let mut rng = StepRng::new(0, 1);
+        let pool = NamePool { first: vec!["Ada".to_string(), "Alan".to_string()], last: vec!["Lovelace".to_string()] };
+        assert!(pool.validate().is_ok());
+        assert!(NamePool { first: Vec::new(), last: vec!["Lovelace".to_string()] }.validate().is_err());
+
+        // Everybody gets a different name until the pool runs dry
+        let mut taken = Vec::new();
+        for _hire in 0..4 {
+            let name = pool.generate(&mut rng, &taken);
+            assert!(!taken.contains(&name));
+            taken.push(name);
+        }
+        assert!(taken.contains(&"Alan Lovelace".to_string()));
+        assert!(taken.contains(&"Ada Lovelace 2".to_string()));
+    }
+
+}
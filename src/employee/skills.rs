@@ -0,0 +1,221 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::EmployeeType;
+
+// Fit of an ordinary team for its work - better than this lifts productivity, worse drags it
+//
+const TYPICAL_FIT: u16 = 55;
+const MIN_FIT_FACTOR: u16 = 50;
+const MAX_FIT_FACTOR: u16 = 130;
+
+// How far a specialism lifts a skill above what's typical for the role
+//
+pub const SPECIALISM_BONUS: u16 = 25;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Skill {
+    Frontend,
+    Backend,
+    Infrastructure,
+    Security,
+    Testing,
+    Design,
+    Sales,
+    Marketing
+}
+
+impl Skill {
+
+    pub fn all() -> [Skill; 8] {
+        [Skill::Frontend, Skill::Backend, Skill::Infrastructure, Skill::Security, Skill::Testing, Skill::Design, Skill::Sales, Skill::Marketing]
+    }
+
+    fn index(&self) -> usize {
+        Skill::all().iter().position(|skill| skill == self).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Skill {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// The kinds of work a team can be doing and the skills each one needs (weights add up to 100)
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Work {
+    Features,
+    Operations,
+    SecurityFixes,                  // getting vulnerable dependencies up to date
+    Selling,
+    Marketing
+}
+
+impl Work {
+
+    pub fn needs(&self) -> Vec<(Skill, u16)> {
+        match self {
+            Work::Features => vec![(Skill::Frontend, 35), (Skill::Backend, 35), (Skill::Design, 15), (Skill::Testing, 15)],
+            Work::Operations => vec![(Skill::Infrastructure, 60), (Skill::Security, 20), (Skill::Backend, 20)],
+            Work::SecurityFixes => vec![(Skill::Security, 50), (Skill::Backend, 30), (Skill::Infrastructure, 20)],
+            Work::Selling => vec![(Skill::Sales, 80), (Skill::Marketing, 20)],
+            Work::Marketing => vec![(Skill::Marketing, 80), (Skill::Design, 20)]
+        }
+    }
+
+    // What somebody in the role does when nobody has told them otherwise
+    //
+    pub fn typical(employee_type: EmployeeType) -> Work {
+        match employee_type {
+            EmployeeType::Administrator => Work::Operations,
+            EmployeeType::Salesperson | EmployeeType::Accountant | EmployeeType::CEO | EmployeeType::FinanceDirector => Work::Selling,
+            EmployeeType::Marketeer | EmployeeType::CMO => Work::Marketing,
+            _ => Work::Features
+        }
+    }
+
+    // People do the part of the work they're best at, so that's the skill they get better at
+    //
+    pub fn exercises(&self, skills: &Skills) -> Skill {
+        self.needs().iter().max_by_key(|(skill, weight)| (skills.level(*skill), *weight)).map(|(skill, _)| *skill).unwrap_or(Skill::Backend)
+    }
+}
+
+impl fmt::Display for Work {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// How good somebody is at each skill (0 - 100)
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Skills {
+    _levels: [u16; 8]
+}
+
+impl Skills {
+
+    pub fn new(levels: &[(Skill, u16)]) -> Skills {
+        let mut skills = Skills { _levels: [0; 8] };
+        for (skill, level) in levels {
+            skills.set(*skill, *level);
+        }
+        skills
+    }
+
+    // Somebody typical in the role
+    //
+    pub fn typical(employee_type: EmployeeType) -> Skills {
+        Skills::new(&match employee_type {
+            EmployeeType::Developer => vec![(Skill::Frontend, 60), (Skill::Backend, 60), (Skill::Infrastructure, 40), (Skill::Security, 30), (Skill::Testing, 40), (Skill::Design, 40)],
+            EmployeeType::Tester => vec![(Skill::Testing, 80), (Skill::Frontend, 30), (Skill::Backend, 20), (Skill::Security, 30), (Skill::Design, 30)],
+            EmployeeType::Administrator => vec![(Skill::Infrastructure, 80), (Skill::Security, 50), (Skill::Backend, 30)],
+            EmployeeType::Salesperson => vec![(Skill::Sales, 70), (Skill::Marketing, 30)],
+            EmployeeType::Marketeer => vec![(Skill::Marketing, 70), (Skill::Design, 40), (Skill::Sales, 30)],
+            EmployeeType::Accountant => vec![(Skill::Sales, 20)],
+            EmployeeType::ProductOwner | EmployeeType::ProductManager => vec![(Skill::Design, 70), (Skill::Frontend, 30), (Skill::Testing, 40), (Skill::Sales, 30), (Skill::Marketing, 30)],
+            EmployeeType::CTO => vec![(Skill::Backend, 70), (Skill::Infrastructure, 60), (Skill::Security, 50), (Skill::Frontend, 40), (Skill::Testing, 30)],
+            EmployeeType::CPO => vec![(Skill::Design, 80), (Skill::Frontend, 40), (Skill::Marketing, 40)],
+            EmployeeType::CMO => vec![(Skill::Marketing, 80), (Skill::Sales, 50), (Skill::Design, 40)],
+            EmployeeType::CEO => vec![(Skill::Sales, 60), (Skill::Marketing, 50)],
+            EmployeeType::FinanceDirector => vec![(Skill::Sales, 40)]
+        })
+    }
+
+    // The skills a role can specialise in - the ones it's typically any good at
+    //
+    pub fn specialisms(employee_type: EmployeeType) -> Vec<Skill> {
+        let typical = Skills::typical(employee_type);
+        Skill::all().iter().filter(|skill| typical.level(**skill) >= 40).copied().collect()
+    }
+
+    pub fn level(&self, skill: Skill) -> u16 {
+        self._levels[skill.index()]
+    }
+
+    pub fn set(&mut self, skill: Skill, level: u16) {
+        self._levels[skill.index()] = u16::min(100, level);
+    }
+
+    pub fn improve(&mut self, skill: Skill, points: u16) {
+        self.set(skill, self.level(skill) + points);
+    }
+
+    // Their strongest skill
+    //
+    pub fn best(&self) -> Skill {
+        *Skill::all().iter().max_by_key(|skill| (self.level(**skill), std::cmp::Reverse(skill.index()))).unwrap_or(&Skill::Backend)
+    }
+
+    pub fn overrides(&mut self, levels: &HashMap<Skill, u16>) {
+        for (skill, level) in levels {
+            self.set(*skill, *level);
+        }
+    }
+}
+
+// How well a group of people covers the skills some work needs (0 - 100). For each
+// skill it's the best person that counts - that's who the others go to.
+//
+pub fn fit(work: Work, members: &[&Skills]) -> u16 {
+    if members.is_empty() {
+        return 0
+    }
+
+    work.needs().iter()
+        .map(|(skill, weight)| members.iter().map(|skills| skills.level(*skill)).max().unwrap_or(0) * weight / 100)
+        .sum()
+}
+
+// Percentage productivity for a team with this fit - 100 for a typical team
+//
+pub fn fit_factor(fit: u16) -> u16 {
+    i32::clamp(100 + fit as i32 - TYPICAL_FIT as i32, MIN_FIT_FACTOR as i32, MAX_FIT_FACTOR as i32) as u16
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn skills_test() {
+        let mut developer = Skills::typical(EmployeeType::Developer);
+        assert_eq!(developer.level(Skill::Backend), 60);
+        assert_eq!(developer.level(Skill::Sales), 0);
+        assert_eq!(developer.best(), Skill::Frontend);
+
+        developer.improve(Skill::Security, 90);
+        assert_eq!(developer.level(Skill::Security), 100);
+        assert_eq!(developer.best(), Skill::Security);
+
+        assert!(Skills::specialisms(EmployeeType::Developer).contains(&Skill::Design));
+        assert!(!Skills::specialisms(EmployeeType::Developer).contains(&Skill::Security));
+
+        assert_eq!(Work::Operations.exercises(&developer), Skill::Security);
+        assert_eq!(Work::typical(EmployeeType::CMO), Work::Marketing);
+    }
+
+    #[test]
+    fn fit_test() {
+        let developer = Skills::typical(EmployeeType::Developer);
+        let administrator = Skills::typical(EmployeeType::Administrator);
+
+        assert_eq!(fit(Work::Features, &[]), 0);
+        assert_eq!(fit(Work::Features, &[&developer]), 54);
+        assert_eq!(fit(Work::Operations, &[&developer]), 42);
+        assert_eq!(fit(Work::Operations, &[&developer, &administrator]), 70);
+
+        assert_eq!(fit_factor(TYPICAL_FIT), 100);
+        assert_eq!(fit_factor(70), 115);
+        assert_eq!(fit_factor(42), 87);
+        assert_eq!(fit_factor(0), MIN_FIT_FACTOR);
+    }
+
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::company::Company;
+use crate::software::Software;
+use crate::world::World;
+
+// How many scores the table keeps
+//
+const TABLE_SIZE: usize = 10;
+
+const FILE_NAME: &str = ".cto-game-highscores.toml";
+
+// One finished game - enough to tell what happened and replay it from the seed
+//
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: u32,
+    pub seed: u64,
+    pub scenario: String,
+    pub outcome: String,
+    pub finished: String,           // year-week the game ended
+    pub weeks: u32,
+    pub valuation: u32,
+    pub customers: u32,
+    pub employees: u16,
+    #[serde(default)]
+    pub achievements: Vec<String>
+}
+
+impl HighScore {
+
+    pub fn from_game(name: &str, world: &World, company: &Company, software: &Software) -> HighScore {
+        HighScore { score: world.score(company, software).total,
+                    seed: world.seed(),
+                    scenario: name.to_string(),
+                    outcome: world.world_state().to_string(),
+                    finished: world.game_year_week(),
+                    weeks: world.weeks_played(),
+                    valuation: world.valuation(company, software),
+                    customers: software.customers(),
+                    employees: company.get_employees().len() as u16,
+                    achievements: world.achievements().unlocked().iter().map(|(achievement, _)| achievement.to_string()).collect() }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    #[serde(default, rename = "scores")]
+    _scores: Vec<HighScore>         // best first
+}
+
+impl HighScores {
+
+    pub fn new() -> HighScores {
+        HighScores { _scores: Vec::new() }
+    }
+
+    pub fn scores(&self) -> &Vec<HighScore> {
+        &self._scores
+    }
+
+    // Kept in the home directory, or wherever we are if there isn't one
+    //
+    pub fn default_path() -> PathBuf {
+        match std::env::var_os("HOME") {
+            Some(home) => Path::new(&home).join(FILE_NAME),
+            None => PathBuf::from(FILE_NAME)
+        }
+    }
+
+    // No file yet just means no scores yet
+    //
+    pub fn load(path: &Path) -> Result<HighScores, String> {
+        if !path.exists() {
+            return Ok(HighScores::new())
+        }
+
+        let text = std::fs::read_to_string(path).map_err(|error| format!("Can't read {}: {}", path.display(), error))?;
+        toml::from_str(&text).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|error| error.to_string())?;
+        std::fs::write(path, text).map_err(|error| format!("Can't write {}: {}", path.display(), error))
+    }
+
+    // Add a score to the table - returns its position, or None if it didn't make the cut
+    //
+    pub fn add(&mut self, score: HighScore) -> Option<usize> {
+        let position = self._scores.iter().position(|existing| existing.score < score.score).unwrap_or(self._scores.len());
+        if position >= TABLE_SIZE {
+            return None
+        }
+
+        self._scores.insert(position, score);
+        self._scores.truncate(TABLE_SIZE);
+        return Some(position)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn high_score(score: u32) -> HighScore {
+        HighScore { score, seed: 42, scenario: "Sandbox (Bootstrapped)".to_string(), outcome: "Won".to_string(), finished: "2003-10".to_string(),
+                    weeks: 165, valuation: 1000000, customers: 500, employees: 12, achievements: vec!["Shipped it".to_string()] }
+    }
+
+    #[test]
+    fn high_score_table_test() {
+        let mut table = HighScores::new();
+        for score in 1..=TABLE_SIZE as u32 {
+            assert!(table.add(high_score(score * 100)).is_some());
+        }
+
+        assert_eq!(table.add(high_score(50)), None);
+        assert_eq!(table.add(high_score(550)), Some(5));
+        assert_eq!(table.scores().len(), TABLE_SIZE);
+        assert_eq!(table.scores()[0].score, 1000);
+        assert_eq!(table.scores().last().unwrap().score, 200);
+    }
+
+    #[test]
+    fn high_score_file_test() {
+        let path = std::env::temp_dir().join(format!("cto-game-highscores-{}.toml", std::process::id()));
+        assert!(HighScores::load(&path).unwrap().scores().is_empty());
+
+        let mut table = HighScores::new();
+        table.add(high_score(1234));
+        table.save(&path).unwrap();
+
+        let loaded = HighScores::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.scores(), table.scores());
+    }
+
+}
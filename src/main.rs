@@ -1,13 +1,30 @@
 // https://github.com/ihalila/pancurses
 //
+// Explicit returns and the exec role names (CEO, CTO...) are house style.
+//
+#![allow(clippy::needless_return, clippy::upper_case_acronyms)]
+
 extern crate pancurses;
 use pancurses::*;
 
 use crate::company::Company;
 use crate::software::Software;
-use crate::world::World;
-use crate::employee::Employee;
+use crate::software::customers::{ChurnReason, Segment};
+use crate::world::{World, WorldState};
+use crate::world::timeframe::YearWeek;
+use crate::world::messages::Severity;
 use crate::employee::EmployeeType;
+use crate::company::CompanyDirection;
+use crate::company::team::{self, Mission, TeamType};
+use crate::company::org;
+use crate::employee::Employee;
+use crate::setup::GameSetup;
+use crate::scenario::Scenario;
+use crate::highscores::{HighScore, HighScores};
+use crate::roster::Roster;
+use crate::screens::{Dialog, Navigator, Screen, View};
+use crate::employee::skills::Skill;
+use crate::employee::contract::Supplier;
 
 use chrono::Local;
 
@@ -15,8 +32,24 @@ mod employee;
 mod software;
 mod world;
 mod company;
+mod setup;
+mod scenario;
+mod highscores;
+mod roster;
+mod screens;
 
 
+// Colour pairs for message severities
+//
+fn severity_colour(severity: Severity) -> chtype {
+  match severity {
+    Severity::Info => COLOR_PAIR(1),
+    Severity::Good => COLOR_PAIR(2),
+    Severity::Warning => COLOR_PAIR(3),
+    Severity::Critical => COLOR_PAIR(4)
+  }
+}
+
 fn draw_hud(_company: &Company, _software: &Software, _world: &World, _window: &Window) {
 
 
@@ -28,6 +61,15 @@ fn draw_hud(_company: &Company, _software: &Software, _world: &World, _window: &
   _window.mvaddstr(6, 1, "Marketers:");
   _window.mvaddstr(7, 1, "Salespeople:");  
   _window.mvaddstr(8, 1, "Product Management:");  
+  _window.mvaddstr(10, 1, "Upgrade Focus:");
+  _window.mvaddstr(11, 1, "Open Deals:");
+  _window.mvaddstr(12, 1, "Pipeline Value:");
+  _window.mvaddstr(13, 1, "Deals Won / Lost:");
+  _window.mvaddstr(14, 1, "Annual Reviews:");
+  _window.mvaddstr(15, 1, "Bonuses Due:");
+  _window.mvaddstr(16, 1, "Contractors (cost):");
+  _window.mvaddstr(17, 1, "Workplace (rent):");
+  _window.mvaddstr(18, 1, "Offices / Hiring In:");
 
 
   let mut developers = 0;
@@ -65,6 +107,16 @@ fn draw_hud(_company: &Company, _software: &Software, _world: &World, _window: &
   _window.mvaddstr(6, first_column_results_pos, marketers.to_string());
   _window.mvaddstr(7, first_column_results_pos, salespeople.to_string());
   _window.mvaddstr(8, first_column_results_pos, product.to_string());
+  _window.mvaddstr(10, first_column_results_pos, format!("{:>3}%", _company.upgrade_focus()));
+  _window.mvaddstr(11, first_column_results_pos, _company.sales_pipeline().open_deals().to_string());
+  _window.mvaddstr(12, first_column_results_pos, _company.sales_pipeline().pipeline_value().to_string());
+  _window.mvaddstr(13, first_column_results_pos, format!("{} / {}", _company.sales_pipeline().won(), _company.sales_pipeline().lost()));
+  _window.mvaddstr(14, first_column_results_pos, format!("{:<7}", if _company.reviews_enabled() { "on" } else { "skipped" }));
+  _window.mvaddstr(15, first_column_results_pos, format!("{:<7}", _company.pending_bonuses()));
+  let contractors = _company.contractors();
+  _window.mvaddstr(16, first_column_results_pos, format!("{:<12}", format!("{} ({})", contractors.len(), contractors.iter().map(|employee| employee.salary()).sum::<u32>())));
+  _window.mvaddstr(17, first_column_results_pos, format!("{:<12}", format!("{} ({})", _company.policy(), _company.office_costs())));
+  _window.mvaddstr(18, first_column_results_pos, format!("{:<12}", format!("{} / {}", _company.offices().len(), _company.hiring_region())));
 
   let second_column_pos  = _window.get_max_x() / 2;
   _window.mvaddstr(1, second_column_pos  , "Cash In Bank:");
@@ -73,19 +125,42 @@ fn draw_hud(_company: &Company, _software: &Software, _world: &World, _window: &
   _window.mvaddstr(4, second_column_pos  , "Lines of Code:");
   _window.mvaddstr(5, second_column_pos  , "Age of Code:");
   _window.mvaddstr(6, second_column_pos  , "Code Complexity:");
-  _window.mvaddstr(7, second_column_pos  , "Dev Capacity:");
+  _window.mvaddstr(7, second_column_pos  , "Dev Capacity (eff/devs):");
   _window.mvaddstr(8, second_column_pos  , "Quality:");
+  _window.mvaddstr(9, second_column_pos  , "Outdated Deps:");
+  _window.mvaddstr(10, second_column_pos , "Security Risk:");
+  _window.mvaddstr(11, second_column_pos , "Active Users:");
+  _window.mvaddstr(12, second_column_pos , "Monthly Revenue:");
+  _window.mvaddstr(13, second_column_pos , "Satisfaction:");
+  _window.mvaddstr(14, second_column_pos , "Churn Rate:");
 
 
   let second_column_results_pos = second_column_pos + 30;
+  let capacity = _company.get_development_capacity(Mission::Product, _software.reliability(), _software.quality(), _software.architecture());
   _window.mvaddstr(1, second_column_results_pos, format!("{:>7}", _company.cash_in_bank().to_string()));
   _window.mvaddstr(2, second_column_results_pos, format!("{:>7}", _software.customers().to_string()));
   _window.mvaddstr(3, second_column_results_pos, format!("{:>7}", _software.cost_of_service().to_string()));
   _window.mvaddstr(4, second_column_results_pos, format!("{:>7}", _software.lines_of_code().to_string()));
   _window.mvaddstr(5, second_column_results_pos, format!("{:>7}", _software.age_of_code().to_string()));
   _window.mvaddstr(6, second_column_results_pos, format!("{:>7}", _software.complexity_of_code().to_string()));
-  _window.mvaddstr(7, second_column_results_pos, format!("{:>7}", _company.get_development_capacity(_software.reliability(), _software.quality()).to_string()));
+  _window.mvaddstr(7, second_column_results_pos, format!("{:>7}", format!("{}/{} {}%", capacity.effective_developers, capacity.developers, capacity.productivity)));
   _window.mvaddstr(8, second_column_results_pos, format!("{:>7}", _software.quality().to_string()));
+  _window.mvaddstr(9, second_column_results_pos, format!("{:>7}", format!("{}/{}", _software.outdated_dependencies(), _software.dependencies())));
+  _window.mvaddstr(10, second_column_results_pos, format!("{:>7}", _software.security_risk().to_string()));
+  _window.mvaddstr(11, second_column_results_pos, format!("{:>7}", _software.active_users().to_string()));
+  _window.mvaddstr(12, second_column_results_pos, format!("{:>7}", _software.monthly_recurring_revenue().to_string()));
+  _window.mvaddstr(13, second_column_results_pos, format!("{:>7}", _software.customer_satisfaction().to_string()));
+  _window.mvaddstr(14, second_column_results_pos, format!("{:>6.1}%", _software.churn_rate()));
+
+  // Why customers have been leaving us
+  //
+  let churn_reasons = _software.customer_base().churn_reasons();
+  let churn_breakdown = ChurnReason::all().iter()
+                          .map(|reason| format!("{} {}", reason, churn_reasons.get(reason).unwrap_or(&0)))
+                          .collect::<Vec<String>>()
+                          .join("  ");
+  _window.mvaddstr(15, second_column_pos, "Churn Reasons:");
+  _window.mvaddstr(16, second_column_pos, format!("{:.1$}", churn_breakdown, (_window.get_max_x() - second_column_pos - 1) as usize));
 
 
   // World
@@ -95,6 +170,7 @@ fn draw_hud(_company: &Company, _software: &Software, _world: &World, _window: &
   _window.mvaddstr(_window.get_max_y() - 8, 1, "Monetization Model:");
 
 
+  _window.mvaddstr(_window.get_max_y() - 7, 1, "Game State:");
   _window.mvaddstr(_window.get_max_y() - 6, 1, "Global Economony:");
   _window.mvaddstr(_window.get_max_y() - 5, 1, "Competition:");
   _window.mvaddstr(_window.get_max_y() - 4, 1, "Job Market:");
@@ -106,12 +182,15 @@ fn draw_hud(_company: &Company, _software: &Software, _world: &World, _window: &
   _window.mvaddstr(_window.get_max_y() - 6, second_column_pos, "Game Ticks:");
   _window.mvaddstr(_window.get_max_y() - 7, second_column_pos, "Game Time:");
 
-  //_window.mvaddstr(_window.get_max_y() - 9, first_column_results_pos, _software.get_architecture());
+  _window.mvaddstr(_window.get_max_y() - 9, first_column_results_pos, format!("{:<14}", _software.architecture().to_string()));
 
+  _window.mvaddstr(_window.get_max_y() - 7, first_column_results_pos, format!("{:<8}", _world.world_state().to_string()));
   _window.mvaddstr(_window.get_max_y() - 6, first_column_results_pos, _world.global_economic_factors().to_string());
   _window.mvaddstr(_window.get_max_y() - 5, first_column_results_pos, _world.competition_in_market().to_string());
   _window.mvaddstr(_window.get_max_y() - 4, first_column_results_pos, _world.job_market().to_string());
-  _window.mvaddstr(_window.get_max_y() - 3, first_column_results_pos, _world.speed().to_string());
+  let speed = if _world.is_paused() { "paused".to_string() } else { _world.game_speed().to_string() };
+  let auto_pause = if _world.auto_pause() { "auto-pause" } else { "" };
+  _window.mvaddstr(_window.get_max_y() - 3, first_column_results_pos, format!("{:<7} {:<10}", speed, auto_pause));
 
 
   _window.mvaddstr(_window.get_max_y() - 3, second_column_results_pos, format!("{}-{:>2} ({})", _world.game_year(), _world.game_month(), _world.game_week()));
@@ -122,6 +201,585 @@ fn draw_hud(_company: &Company, _software: &Software, _world: &World, _window: &
 
 }
 
+fn draw_competitors(_world: &World, _window: &Window) {
+
+  _window.mvaddstr(1, 1, "Competitor Intelligence");
+
+  let columns = [1, 22, 31, 41, 49, 61, 72, 82];
+  let headings = ["Name", "Quality", "Features", "Price", "Funding", "Aggression", "Strength", "Last Move"];
+  for (column, heading) in columns.iter().zip(headings.iter()) {
+    _window.mvaddstr(3, *column, heading);
+  }
+
+  for (row, competitor) in _world.competitors().iter().enumerate() {
+    let y_pos = 4 + row as i32;
+    let last_move = match competitor.last_action() {
+      Some(action) => action.to_string(),
+      None => "-".to_string()
+    };
+
+    _window.mvaddstr(y_pos, columns[0], competitor.name());
+    _window.mvaddstr(y_pos, columns[1], format!("{:>7}", competitor.product_quality()));
+    _window.mvaddstr(y_pos, columns[2], format!("{:>8}", competitor.feature_richness()));
+    _window.mvaddstr(y_pos, columns[3], format!("{:>4}%", competitor.price()));
+    _window.mvaddstr(y_pos, columns[4], format!("{:>10}", competitor.funding()));
+    _window.mvaddstr(y_pos, columns[5], format!("{:>10}", competitor.aggressiveness()));
+    _window.mvaddstr(y_pos, columns[6], format!("{:>8}", competitor.strength()));
+    _window.mvaddstr(y_pos, columns[7], format!("{:<16}", last_move));
+  }
+
+  _window.mvaddstr(_window.get_max_y() - 4, 1, "Competition:");
+  _window.mvaddstr(_window.get_max_y() - 4, 22, format!("{:>7}", _world.competition_in_market()));
+  _window.mvaddstr(_window.get_max_y() - 3, 1, "Market Price:");
+  _window.mvaddstr(_window.get_max_y() - 3, 22, format!("{:>6}%", world::competitor::market_price(_world.competitors())));
+}
+
+fn draw_economy(_world: &World, _window: &Window) {
+
+  let economy = _world.economy();
+
+  _window.mvaddstr(1, 1, "Global Economy");
+
+  _window.mvaddstr(3, 1, "Phase:");
+  _window.mvaddstr(4, 1, "Economic Index:");
+  _window.mvaddstr(5, 1, "Interest Rate:");
+  _window.mvaddstr(6, 1, "Customer Budgets:");
+  _window.mvaddstr(7, 1, "Funding Availability:");
+  _window.mvaddstr(8, 1, "Salary Inflation:");
+  _window.mvaddstr(9, 1, "Job Market:");
+
+  let results_pos = 30;
+  _window.mvaddstr(3, results_pos, format!("{:<10}", economy.phase().to_string()));
+  _window.mvaddstr(4, results_pos, format!("{:>7}", economy.index()));
+  _window.mvaddstr(5, results_pos, format!("{:>6.2}%", economy.interest_rate() as f32 / 100.0f32));
+  _window.mvaddstr(6, results_pos, format!("{:>6}%", economy.customer_budget()));
+  _window.mvaddstr(7, results_pos, format!("{:>6}%", economy.funding_availability()));
+  _window.mvaddstr(8, results_pos, format!("{:>6}%", economy.salary_inflation()));
+  _window.mvaddstr(9, results_pos, format!("{:>7}", _world.job_market()));
+
+  // Trend chart of the economic index - newest week on the right
+  //
+  let chart_height = 10;
+  let chart_top = 11;
+  let chart_left = 6;
+  let chart_width = (_window.get_max_x() - chart_left - 1) as usize;
+
+  let history = economy.history();
+  let visible = &history[history.len().saturating_sub(chart_width)..];
+
+  for row in 0..chart_height {
+    let level = 1000 - row * 1000 / chart_height;
+    _window.mvaddstr(chart_top + row, 0, format!("{:>4} ", if row % 3 == 0 { level.to_string() } else { String::new() }));
+    _window.mvaddch(chart_top + row, chart_left - 1, '|');
+
+    let line = visible.iter()
+                      .map(|index| if *index as i32 >= level { '#' } else { ' ' })
+                      .collect::<String>();
+    _window.mvaddstr(chart_top + row, chart_left, line);
+  }
+  _window.mvaddstr(chart_top + chart_height, chart_left - 1, format!("+{}", "-".repeat(chart_width)));
+  _window.mvaddstr(chart_top + chart_height + 1, chart_left, format!("last {} weeks", visible.len()));
+}
+
+// The oldest event waiting on a decision, in a box of its own over whatever screen is
+// showing. Returns the box so it can go out with the rest of the frame.
+//
+fn draw_decision(_world: &World, _window: &Window) -> Option<Window> {
+
+  let pending = _world.events().pending().first()?;
+  let definition = pending.definition();
+  let weeks_left = world::events::DECISION_WEEKS.saturating_sub(pending.raised().difference_weeks(&_world.current_yearweek()));
+  let fallback = definition.choices.last().map(|choice| choice.label).unwrap_or_default();
+
+  // Left for the status bar to mention if the terminal is too small to show it
+  //
+  let width = i32::min(76, _window.get_max_x() - 4).max(0);
+  let height = 7 + definition.choices.len() as i32;
+  if width < 24 || height > _window.get_max_y() {
+    return None;
+  }
+  let dialog = newwin(height, width, (_window.get_max_y() - height) / 2, (_window.get_max_x() - width) / 2);
+  let text_width = (width - 4) as usize;
+
+  dialog.draw_box(0, 0);
+  dialog.attron(A_BOLD);
+  dialog.mvaddstr(1, 2, format!("{:.1$}", definition.title, text_width));
+  dialog.attroff(A_BOLD);
+  dialog.mvaddstr(2, 2, format!("{:.1$}", format!("Raised {} - '{}' in {} weeks if nobody decides", pending.raised(), fallback, weeks_left), text_width));
+
+  for (index, choice) in definition.choices.iter().enumerate() {
+    dialog.mvaddstr(4 + index as i32, 4, format!("{:.1$}", format!("[{}] {}", index + 1, choice.label), text_width - 2));
+  }
+  dialog.mvaddstr(height - 2, 2, format!("{:.1$}", "Number to decide, Esc to put it away (D brings it back)", text_width));
+
+  Some(dialog)
+}
+
+// Tabs across the top, the status bar along the bottom and the screens in between -
+// each draws into its own part of the terminal
+//
+fn split_window(_window: &Window) -> Option<(Window, Window, Window)> {
+  let (rows, columns) = _window.get_max_yx();
+  match (_window.derwin(2, columns, 0, 0), _window.derwin(rows - 3, columns, 2, 0), _window.derwin(1, columns, rows - 1, 0)) {
+    (Ok(tabs), Ok(content), Ok(status)) => Some((tabs, content, status)),
+    _ => None
+  }
+}
+
+// Screens along the top with the current one picked out, its pages underneath and the
+// time on the right
+//
+fn draw_tabs(_navigator: &Navigator, _window: &Window) {
+
+  _window.erase();
+  _window.mv(0, 1);
+  for (index, screen) in Screen::all().iter().enumerate() {
+    let attributes = if *screen == _navigator.screen() { A_REVERSE } else { A_NORMAL };
+    _window.attron(attributes);
+    _window.addstr(format!(" F{} {} ", index + 1, screen));
+    _window.attroff(attributes);
+    _window.addstr(" ");
+  }
+
+  let format_time = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+  _window.mvaddstr(0, _window.get_max_x() - 20, format_time);
+
+  let pages = _navigator.screen().views();
+  _window.mv(1, 2);
+  for (index, view) in pages.iter().enumerate() {
+    if index > 0 {
+      _window.addstr(" | ");
+    }
+    let attributes = if *view == _navigator.view() { A_BOLD | A_UNDERLINE } else { A_NORMAL };
+    _window.attron(attributes);
+    _window.addstr(view.to_string());
+    _window.attroff(attributes);
+  }
+  if pages.len() > 1 {
+    _window.addstr("   (Left / Right for the other pages)");
+  }
+}
+
+// Always along the bottom - where the game is at and the latest news, unless there's
+// something more important to say
+//
+fn draw_status_bar(_company: &Company, _software: &Software, _world: &World, _window: &Window, notice: Option<&String>) {
+
+  let speed = if _world.is_paused() { "paused".to_string() } else { _world.game_speed().to_string() };
+  let waiting = match _world.events().pending().len() {
+    0 => String::new(),
+    1 => " | 1 decision waiting (D)".to_string(),
+    decisions => format!(" | {} decisions waiting (D)", decisions)
+  };
+  let news = notice.cloned().or(_world.messages().messages().back().map(|message| message.text.clone())).unwrap_or_default();
+
+  let status = format!(" {} {} | Cash {} | Customers {}{} | {}", _world.game_year_week(), speed, _company.cash_in_bank(), _software.customers(), waiting, news);
+  _window.attron(A_REVERSE);
+  _window.mvaddstr(0, 0, format!("{:<1$.1$}", status, _window.get_max_x() as usize));
+  _window.attroff(A_REVERSE);
+}
+
+// Where the code is at - what's been built, what it's built on and who is building it
+//
+fn draw_product(_company: &Company, _software: &Software, _window: &Window) {
+
+  _window.mvaddstr(1, 1, "Product  (w rearchitect, u upgrade focus)");
+
+  let capacity = |mission: Mission| {
+    let capacity = _company.get_development_capacity(mission, _software.reliability(), _software.quality(), _software.architecture());
+    format!("{}/{} at {}%", capacity.effective_developers, capacity.developers, capacity.productivity)
+  };
+
+  let architecture = match _software.migrating_to() {
+    Some(target) => format!("{} -> {} ({} days left)", _software.architecture(), target, _software.migration_days()),
+    None => _software.architecture().to_string()
+  };
+
+  let rows = [("Architecture:", architecture),
+              ("Releases:", _software.releases().to_string()),
+              ("Lines of Code:", _software.lines_of_code().to_string()),
+              ("Age of Code:", _software.age_of_code().to_string()),
+              ("Code Complexity:", _software.complexity_of_code().to_string()),
+              ("Quality:", _software.quality().to_string()),
+              ("Technical Debt:", _software.technical_debt().to_string()),
+              ("Feature Richness:", _software.feature_richness().to_string()),
+              ("Usability:", _software.usability_factor().to_string()),
+              ("Reliability:", _software.reliability().to_string()),
+              ("Security Risk:", _software.security_risk().to_string()),
+              ("Outdated Deps:", format!("{}/{}", _software.outdated_dependencies(), _software.dependencies())),
+              ("Upgrade Focus:", format!("{}%", _company.upgrade_focus())),
+              ("Product Capacity:", capacity(Mission::Product)),
+              ("Platform Capacity:", capacity(Mission::Platform)),
+              ("Collaboration:", format!("{}% ({})", _company.collaboration(), _company.policy()))];
+
+  let results_pos = 30;
+  for (row, (label, value)) in rows.iter().enumerate() {
+    _window.mvaddstr(3 + row as i32, 1, label);
+    _window.mvaddstr(3 + row as i32, results_pos, format!("{:<20}", value));
+  }
+}
+
+// What comes in and goes out every month, and how long the money lasts
+//
+fn draw_finance(_company: &Company, _software: &Software, _world: &World, _window: &Window) {
+
+  _window.mvaddstr(1, 1, "Finance  (r raise funding, i IPO)");
+
+  let (contractors, staff): (Vec<&Employee>, Vec<&Employee>) = _company.get_employees().values().partition(|employee| employee.is_contractor());
+  let salaries = staff.iter().map(|employee| employee.salary()).sum::<u32>();
+  let contracts = contractors.iter().map(|employee| employee.salary()).sum::<u32>();
+  let revenue = _software.monthly_recurring_revenue();
+  let commission = _company.sales_pipeline().commission_due();
+  let outgoings = salaries + contracts + _company.office_costs() + _company.pending_bonuses() + commission;
+
+  let accounts = Segment::all().iter()
+                   .map(|segment| format!("{} {}", segment, _software.customer_base().accounts().iter().filter(|account| account.segment() == *segment).count()))
+                   .collect::<Vec<String>>()
+                   .join("  ");
+
+  let runway = match outgoings.checked_sub(revenue) {
+    Some(burn) if burn > 0 => format!("{} months", _company.cash_in_bank() / burn),
+    _ => "paying our way".to_string()
+  };
+
+  let rows = [("Cash In Bank:", _company.cash_in_bank().to_string()),
+              ("Monthly Revenue:", revenue.to_string()),
+              ("Accounts:", accounts),
+              ("Salaries:", format!("{} ({} staff)", salaries, staff.len())),
+              ("Contractors:", format!("{} ({} contractors)", contracts, contractors.len())),
+              ("Office Rent:", format!("{} ({} offices)", _company.office_costs(), _company.offices().len())),
+              ("Bonuses Due:", _company.pending_bonuses().to_string()),
+              ("Commission Due:", commission.to_string()),
+              ("Next Month:", format!("{} net", revenue as i64 - outgoings as i64)),
+              ("Runway:", runway),
+              ("", String::new()),
+              ("Funding Raised:", _company.funding_raised().to_string()),
+              ("Ownership:", _company.ownership().to_string()),
+              ("Valuation:", _world.valuation(_company, _software).to_string()),
+              ("Options Granted:", format!("{} ({:.1}% of the company)", _company.options_granted(), _company.options_granted() as f32 * 100.0 / employee::career::OPTION_POOL as f32)),
+              ("Profitable Months:", _world.profitable_months().to_string())];
+
+  let results_pos = 30;
+  for (row, (label, value)) in rows.iter().enumerate() {
+    _window.mvaddstr(3 + row as i32, 1, label);
+    _window.mvaddstr(3 + row as i32, results_pos, format!("{:<30}", value));
+  }
+}
+
+fn draw_events(_world: &World, _window: &Window) {
+
+  _window.mvaddstr(1, 1, "Events");
+
+  _window.mvaddstr(3, 1, "Waiting on you:");
+  for (row, pending) in _world.events().pending().iter().enumerate() {
+    _window.mvaddstr(4 + row as i32, 3, format!("{}  {:<60}", pending.raised(), pending.definition().title));
+  }
+
+  let log_top = 6 + _world.events().pending().len() as i32;
+  _window.mvaddstr(log_top, 1, "Event Log:");
+
+  let rows = (_window.get_max_y() - 3 - log_top) as usize;
+  for (row, entry) in _world.events().log().iter().take(rows).enumerate() {
+    _window.mvaddstr(log_top + 1 + row as i32, 3, format!("{}  {:<45} -> {:<35}", entry.yearweek, entry.title, entry.outcome));
+  }
+}
+
+// Scrollable message log - newest at the top, offset by scroll and filtered by severity
+//
+fn draw_messages(_world: &World, _window: &Window, scroll: usize, min_severity: Severity) {
+
+  _window.mvaddstr(1, 1, format!("Messages (showing {} and above - f to filter, PgUp/PgDn to scroll)", min_severity));
+
+  let messages = _world.messages().filtered(min_severity);
+  let rows = (_window.get_max_y() - 6).max(0) as usize;
+  let width = (_window.get_max_x() - 2).max(0) as usize;
+
+  for row in 0..rows {
+    let y_pos = 3 + row as i32;
+    _window.mv(y_pos, 0);
+    _window.clrtoeol();
+
+    if let Some(message) = messages.get(scroll + row) {
+      let line = format!("{}  {:<9} {:<10} {}", message.yearweek, message.category, message.severity, message.text);
+      _window.attron(severity_colour(message.severity));
+      _window.mvaddstr(y_pos, 1, line.chars().take(width).collect::<String>());
+      _window.attroff(severity_colour(message.severity));
+    }
+  }
+}
+
+// Progress on every objective - the final scorecard once the game is over
+//
+fn draw_scorecard(_company: &Company, _software: &Software, _world: &World, _window: &Window) {
+
+  let title = match _world.world_state() {
+    WorldState::Nominal => "Objectives".to_string(),
+    state => format!("Final Scorecard - {}", state)
+  };
+  _window.mvaddstr(1, 1, format!("{:<40}", title));
+
+  _window.mvaddstr(3, 1, "Valuation:");
+  _window.mvaddstr(4, 1, "Ownership:");
+  _window.mvaddstr(5, 1, "Profitable Months:");
+  _window.mvaddstr(6, 1, "Funding Raised:");
+
+  let results_pos = 30;
+  _window.mvaddstr(3, results_pos, format!("{:>10}", _world.valuation(_company, _software)));
+  _window.mvaddstr(4, results_pos, format!("{:>10}", _company.ownership().to_string()));
+  _window.mvaddstr(5, results_pos, format!("{:>10}", _world.profitable_months()));
+  _window.mvaddstr(6, results_pos, format!("{:>10}", _company.funding_raised()));
+
+  let scorecard = _world.scorecard(_company, _software);
+  _window.mvaddstr(8, 1, "To win:");
+
+  let mut y_pos = 9;
+  for entry in scorecard.entries.iter().filter(|entry| entry.win) {
+    _window.mvaddstr(y_pos, 3, format!("{:<60} {:>3}%", entry.description, entry.progress));
+    y_pos += 1;
+  }
+
+  if scorecard.entries.iter().any(|entry| !entry.win) {
+    y_pos += 1;
+    _window.mvaddstr(y_pos, 1, "Lose if:");
+    for entry in scorecard.entries.iter().filter(|entry| !entry.win) {
+      y_pos += 1;
+      _window.mvaddstr(y_pos, 3, format!("{:<60} {:>3}%", entry.description, entry.progress));
+    }
+    y_pos += 1;
+  }
+
+  _window.mvaddstr(y_pos + 1, 1, format!("Objectives: {:>3}%", scorecard.score));
+
+  let score = _world.score(_company, _software);
+  y_pos += 3;
+  _window.mvaddstr(y_pos, 1, format!("Score: {:>10}", score.total));
+  _window.mvaddstr(y_pos + 1, 3, format!("Valuation {}  Wellbeing {}  Satisfaction {}  Achievements {}  Time Bonus {}%",
+                                         score.valuation, score.wellbeing, score.satisfaction, score.achievements, score.time_bonus));
+
+  y_pos += 3;
+  _window.mvaddstr(y_pos, 1, "Achievements:");
+  for (achievement, yearweek) in _world.achievements().unlocked() {
+    y_pos += 1;
+    _window.mvaddstr(y_pos, 3, format!("{}  {:<30} {:>5}", yearweek, achievement.to_string(), achievement.points()));
+  }
+}
+
+// Teams, who is in them and how well they're working - the selected team is marked
+//
+fn draw_teams(_company: &Company, _window: &Window, selected: usize) {
+
+  _window.mvaddstr(1, 1, "Teams  (T form, X disband, M mission, Y type, A add next unassigned, C train, [ ] select)");
+
+  let columns = [1, 4, 20, 30, 42, 50, 64];
+  let headings = ["", "Name", "Type", "Mission", "Size", "Productivity", "Skill Fit"];
+  for (column, heading) in columns.iter().zip(headings.iter()) {
+    _window.mvaddstr(3, *column, heading);
+  }
+
+  let mut y_pos = 4;
+  for (row, team) in _company.teams().iter().enumerate() {
+    _window.mv(y_pos, 0);
+    _window.clrtoeol();
+    _window.mvaddstr(y_pos, columns[0], if row == selected { ">" } else { " " });
+    _window.mvaddstr(y_pos, columns[1], team.name());
+    _window.mvaddstr(y_pos, columns[2], team.team_type().to_string());
+    _window.mvaddstr(y_pos, columns[3], team.mission().to_string());
+    _window.mvaddstr(y_pos, columns[4], format!("{:>4}", team.get_size()));
+    _window.mvaddstr(y_pos, columns[5], format!("{:>12}", team.productivity()));
+    let employees = team.members().iter().filter_map(|id| _company.employee(*id)).collect::<Vec<_>>();
+    _window.mvaddstr(y_pos, columns[6], format!("{:>9}", team::skill_fit(team.mission(), &employees)));
+    y_pos += 1;
+
+    _window.mv(y_pos, 0);
+    _window.clrtoeol();
+    let members = employees.iter()
+                    .map(|employee| format!("{} ({}, {}{})", employee.name(), employee.efficiency(), employee.skills().best(), if employee.is_onboarding() { ", new" } else { "" }))
+                    .collect::<Vec<String>>();
+    _window.mvaddstr(y_pos, columns[1] + 2, format!("Formed {}, changed {}: {}", team.created(), team.updated(), members.join(", ")));
+    y_pos += 1;
+  }
+
+  let unassigned = _company.unassigned_employees().iter().map(|employee| employee.name()).collect::<Vec<String>>();
+  y_pos += 1;
+  _window.mv(y_pos, 0);
+  _window.clrtobot();
+  _window.mvaddstr(y_pos, 1, format!("Unassigned ({}):", unassigned.len()));
+  _window.mvaddstr(y_pos + 1, 3, unassigned.join(", "));
+}
+
+// Columns the roster list takes up, before the detail pane
+//
+const ROSTER_WIDTH: i32 = 84;
+
+// Everybody, one per row, with the one picked out shown in full alongside
+//
+fn draw_roster(_company: &Company, _window: &Window, _roster: &Roster, _yearweek: YearWeek) {
+
+  let filter = _roster.filter().map(|role| format!("{:?}", role)).unwrap_or("everyone".to_string());
+  let order = if _roster.descending() { "descending" } else { "ascending" };
+  _window.mv(1, 0);
+  _window.clrtoeol();
+  let heading = format!("Roster - {} by {} {}  (S sort, R reverse, F filter, C train, E extend contract, B reports to, [ ] select, * contractor)", filter, _roster.sort_by(), order);
+  _window.mvaddstr(1, 1, format!("{:.1$}", heading, (_window.get_max_x() - 2).max(0) as usize));
+  _window.mv(2, 0);
+  _window.clrtoeol();
+  _window.mvaddstr(2, 1, format!("Hiring a {:?} in {}  (H hire, J role, L region)", _company.hiring_role(), _company.hiring_region()));
+
+  let columns = [1, 3, 19, 36, 41, 48, 53, 60, 74];
+  let headings = ["", "Name", "Role", "Age", "Salary", "Eff", "Talent", "Team", "Morale"];
+  for (column, heading) in columns.iter().zip(headings.iter()) {
+    _window.mvaddstr(3, *column, heading);
+  }
+
+  // The detail pane sits beside the list when there's room, otherwise the list gives up
+  // some of its rows to make space for it underneath
+  //
+  let detail_width = 44;
+  let beside = _window.get_max_x() >= ROSTER_WIDTH + detail_width;
+  let list_rows = (_window.get_max_y() - 7).max(0);
+  let list_rows = if beside { list_rows } else { list_rows / 3 };
+
+  // Scroll so whoever is selected stays in view
+  //
+  let rows = _roster.rows(_company);
+  let visible = list_rows as usize;
+  let first = _roster.selected().saturating_sub(visible.saturating_sub(1));
+
+  for line in 0..visible {
+    let y_pos = 4 + line as i32;
+    _window.mv(y_pos, 0);
+    _window.clrtoeol();
+
+    if let Some(employee) = rows.get(first + line) {
+      let team = _company.team_of(employee.id()).and_then(|id| _company.team(id)).map(|team| team.name()).unwrap_or("-".to_string());
+      _window.mvaddstr(y_pos, columns[0], if first + line == _roster.selected() { ">" } else { " " });
+      _window.mvaddstr(y_pos, columns[1], format!("{:.13}{}", employee.name(), if employee.is_contractor() { " *" } else { "" }));
+      _window.mvaddstr(y_pos, columns[2], format!("{:.16}", format!("{:?}", employee.employee_type())));
+      _window.mvaddstr(y_pos, columns[3], format!("{:>3}", employee.age()));
+      _window.mvaddstr(y_pos, columns[4], format!("{:>6}", employee.salary()));
+      _window.mvaddstr(y_pos, columns[5], format!("{:>3}", employee.efficiency()));
+      _window.mvaddstr(y_pos, columns[6], format!("{:>6}", employee.talent()));
+      _window.mvaddstr(y_pos, columns[7], format!("{:.13}", team));
+      _window.mvaddstr(y_pos, columns[8], format!("{:>6}", employee.morale()));
+    }
+  }
+
+  if let Some(employee) = _roster.selected_employee(_company) {
+    let team = _company.team_of(employee.id()).and_then(|id| _company.team(id)).map(|team| format!("{} ({})", team.name(), team.mission())).unwrap_or("none".to_string());
+    let contract = employee.contract().map(|contract| format!("{} until {} ({} weeks left)", contract.supplier, contract.ends, contract.weeks_left(_yearweek))).unwrap_or("permanent".to_string());
+    let last_review = employee.reviews().last().map(|review| format!("{} in {}", review.rating, review.yearweek)).unwrap_or("none yet".to_string());
+
+    let mut lines = vec![format!("{} (#{}) - {:?}, {}", employee.name(), employee.id(), employee.employee_type(), employee.level()),
+                         String::new(),
+                         format!("Age {}, paid {} a year ({} a month)", employee.age(), employee.compensation(), employee.salary()),
+                         format!("Based in {}", employee.region()),
+                         format!("Efficiency {} of a possible {}, talent {}", employee.efficiency(), employee.skill_ceiling(), employee.talent()),
+                         format!("Morale {}, {} share options", employee.morale(), employee.equity()),
+                         format!("Contract: {}", contract),
+                         format!("Team: {}", team),
+                         format!("Reports to: {}", employee.manager().map(|manager| _company.name_of(manager)).unwrap_or("nobody".to_string())),
+                         format!("Onboarding: {}", if employee.is_onboarding() { format!("{} weeks to go", employee.onboarding()) } else { "done".to_string() }),
+                         format!("Last review: {}", last_review),
+                         format!("Reviews missed in a row: {}", employee.skipped_reviews())];
+
+    if employee.employee_type() == EmployeeType::Salesperson {
+      lines.push(format!("Booked this month: {}", _company.sales_pipeline().booked_this_month(employee.id())));
+    }
+
+    lines.push(String::new());
+    lines.push("Skills".to_string());
+
+    for pair in Skill::all().chunks(2) {
+      lines.push(pair.iter().map(|skill| format!("{:<15}{:>3}", skill.to_string(), employee.skills().level(*skill))).collect::<Vec<String>>().join("   "));
+    }
+
+    lines.push(String::new());
+    lines.push("Recent progress".to_string());
+    for change in employee.skill_history().iter().rev().take(5) {
+      lines.push(format!("{}  efficiency {} ({})", change.yearweek, change.efficiency, change.source));
+    }
+
+    let (y_pos, x_pos, width) = match beside {
+      true => (3, ROSTER_WIDTH, detail_width),
+      false => (5 + list_rows, 1, (_window.get_max_x() - 2).max(0))
+    };
+    let height = (_window.get_max_y() - y_pos - 1).max(0) as usize;
+    for (line, text) in lines.iter().take(height).enumerate() {
+      _window.mvaddstr(y_pos + line as i32, x_pos, format!("{:<1$.1$}", text, width as usize));
+    }
+  }
+}
+
+// Who reports to whom, with the span of every manager and how each department is running
+//
+fn draw_org_chart(_company: &Company, _window: &Window) {
+
+  _window.mvaddstr(1, 1, format!("Org Chart (spans of {} to {} reports work best, P promote from within)", org::MIN_SPAN, org::MAX_SPAN));
+
+  // Walk down from everybody who reports to nobody
+  //
+  fn chart_lines(company: &Company, id: u16, depth: usize, lines: &mut Vec<String>) {
+    let employee = &company.get_employees()[&id];
+    let reports = company.reports(id);
+    let span = match reports.len() {
+      0 => String::new(),
+      span => format!("  [{} reports, {}% overhead]", span, org::span_overhead(span))
+    };
+    lines.push(format!("{}{} ({:?}, {}){}", "  ".repeat(depth), employee.name(), employee.employee_type(), employee.level(), span));
+
+    for report in reports {
+      chart_lines(company, report.id(), depth + 1, lines);
+    }
+  }
+
+  let mut roots: Vec<&Employee> = _company.get_employees().values().filter(|employee| employee.manager().is_none()).collect();
+  roots.sort_by_key(|employee| (!org::is_executive(employee.employee_type()), employee.name(), employee.id()));
+
+  let mut lines = Vec::new();
+  for root in roots {
+    chart_lines(_company, root.id(), 0, &mut lines);
+  }
+
+  let rows = (_window.get_max_y() - 6).max(0) as usize;
+  for row in 0..rows {
+    _window.mv(3 + row as i32, 0);
+    _window.clrtoeol();
+    if let Some(line) = lines.get(row) {
+      _window.mvaddstr(3 + row as i32, 1, line);
+    }
+  }
+
+  // No room for the summary on a narrow pane
+  //
+  let summary_pos = _window.get_max_x() - 30;
+  if summary_pos < 1 {
+    return;
+  }
+  _window.mvaddstr(3, summary_pos, "Department Effectiveness");
+  for (row, department) in org::Department::all().iter().enumerate() {
+    _window.mvaddstr(4 + row as i32, summary_pos, format!("{:<15} {:>4}%", department.to_string(), _company.department_effectiveness(*department)));
+  }
+}
+
+// Best games played on this machine
+//
+fn draw_high_scores(high_scores: &HighScores, _window: &Window) {
+
+  _window.mvaddstr(1, 1, "High Scores");
+  _window.mvaddstr(3, 1, format!("{:<3} {:>10}  {:<30} {:<9} {:<8} {:>6} {:>12} {:>9} {:>5}  {:<20}",
+                                 "", "Score", "Game", "Outcome", "Finished", "Weeks", "Valuation", "Customers", "Staff", "Seed"));
+
+  for (row, high_score) in high_scores.scores().iter().enumerate() {
+    _window.mvaddstr(4 + row as i32, 1, format!("{:<3} {:>10}  {:<30} {:<9} {:<8} {:>6} {:>12} {:>9} {:>5}  {:<20}",
+                                                row + 1, high_score.score, high_score.scenario, high_score.outcome, high_score.finished,
+                                                high_score.weeks, high_score.valuation, high_score.customers, high_score.employees, high_score.seed));
+  }
+
+  if high_scores.scores().is_empty() {
+    _window.mvaddstr(4, 1, "No games finished yet");
+  }
+}
+
 fn draw_matrix_workface(mut _company: &Company, _software: &Software, _world: &World, _window: &Window) {
 
   let scale = 6;
@@ -131,7 +789,7 @@ fn draw_matrix_workface(mut _company: &Company, _software: &Software, _world: &W
   let min_y = _window.get_max_y() / 2 - _window.get_max_y() / scale;
   let max_y = _window.get_max_y() / 2 + _window.get_max_y() / scale;
 
-  let horiz_string = std::iter::repeat("-").take((1 + _window.get_max_x() / ( scale as f32 / 2.0f32 ) as i32 ) as usize).collect::<String>();
+  let horiz_string = "-".repeat((1 + _window.get_max_x() / ( scale as f32 / 2.0f32 ) as i32 ) as usize);
   let horiz_string_2 = horiz_string.clone();
   _window.mvaddstr(min_y, min_x, horiz_string );
   _window.mvaddstr(max_y, min_x, horiz_string_2 );
@@ -145,86 +803,454 @@ fn draw_matrix_workface(mut _company: &Company, _software: &Software, _world: &W
 
 }
 
-fn main() {
+// New game screen - pick a difficulty and tweak the start before we begin.
+// Returns None if the player backs out.
+//
+fn run_setup_screen(mut setup: GameSetup, _window: &Window) -> Option<GameSetup> {
 
-  let mut software = Software::new(0, 0, 0, 0);
-  let mut world = World::new(100, 100, 100, 100, 0);
-  let mut company = company::Company::new(100, company::CompanyDirection::B2B);
+  _window.timeout(-1);
+  _window.keypad(true);
+  noecho();
 
-  let dev1 = Employee::new(EmployeeType::Developer, 1, "Developer 1".to_string(), 50, 90, 200, 90);
-  let dev2 = Employee::new(EmployeeType::Developer, 2,  "Developer 2".to_string(), 23, 35, 89, 77);
-  let dev3 = Employee::new(EmployeeType::Developer, 3, "Developer 3".to_string(), 30, 70, 100, 85);
-  let admin1 = Employee::new(EmployeeType::Administrator, 4, "Admin 1".to_string(), 37,  80,  80,  65);
+  // The role the team keys add and remove
+  //
+  let mut role = EmployeeType::Developer;
 
-  company.add_employee(dev1);
-  company.add_employee(dev2);
-  company.add_employee(dev3);
-  company.add_employee(admin1);
+  loop {
+    _window.clear();
+    _window.mvaddstr(1, 1, "New Game  (lower case to raise, upper case to lower)");
+
+    let rows = match &setup.scenario {
+      Some(scenario) => {
+        let conditions = |conditions: &[world::objectives::Condition]| if conditions.is_empty() { "-".to_string() } else {
+          conditions.iter().map(|condition| condition.to_string()).collect::<Vec<String>>().join(", ")
+        };
+        let team = scenario.employees.iter().flat_map(|spec| std::iter::repeat_n(spec.role, spec.count)).collect::<Vec<EmployeeType>>();
+
+        vec![
+          ("[s] Scenario:", format!("{} - {}", scenario.name, scenario.description)),
+          ("Start:", format!("{:04}-{:02}", scenario.world.start_year, scenario.world.start_week)),
+          ("Direction:", scenario.company.direction.to_string()),
+          ("Starting Cash:", scenario.company.cash.to_string()),
+          ("Founding Team:", setup::team_summary(&team)),
+          ("Win When:", conditions(&scenario.win)),
+          ("Lose When:", conditions(&scenario.lose)),
+          ("Inherited Code:", format!("{} lines", scenario.software.lines_of_code))
+        ]
+      },
+      None => vec![
+        ("[s] Scenario:", "None - sandbox".to_string()),
+        ("[d] Difficulty:", format!("{} - {}", setup.difficulty, setup.difficulty.description())),
+        ("[+/-] Start Year:", setup.start_year.to_string()),
+        ("[b] Direction:", setup.direction.to_string()),
+        ("[c/C] Starting Cash:", setup.cash.to_string()),
+        ("[r] Role:", format!("{:?}", role)),
+        ("[t/T] Founding Team:", setup::team_summary(&setup.founding_team)),
+        ("[e/E] Global Economy:", setup.global_economic_factors.to_string()),
+        ("[m/M] Competition:", setup.competition_in_market.to_string()),
+        ("[j/J] Job Market:", setup.job_market.to_string()),
+        ("Inherited Code:", format!("{} lines", setup.lines_of_code)),
+        ("Goals:", setup.goals.iter().map(|goal| goal.to_string()).collect::<Vec<String>>().join(", "))
+      ]
+    };
+
+    for (row, (label, value)) in rows.iter().enumerate() {
+      _window.mvaddstr(3 + row as i32, 1, label);
+      _window.mvaddstr(3 + row as i32, 24, value);
+    }
+
+    _window.mvaddstr(_window.get_max_y() - 2, 1, "Enter to start, Esc to quit");
+    _window.refresh();
+
+    match _window.getch() {
+      Some(Input::Character('\n')) | Some(Input::KeyEnter) => {
+        _window.clear();
+        return Some(setup)
+      },
+      Some(Input::Character('\u{1b}')) => return None,
+      Some(Input::Character('s')) => setup.scenario = Scenario::next_bundled(setup.scenario.as_ref()),
+      Some(Input::Character('d')) => setup.change_difficulty(setup.difficulty.next()),
+      Some(Input::Character('+')) | Some(Input::Character('>')) => setup.set_start_year(setup.start_year + 1),
+      Some(Input::Character('-')) | Some(Input::Character('<')) => setup.set_start_year(i32::max(1970, setup.start_year - 1)),
+      Some(Input::Character('b')) => setup.direction = match setup.direction {
+        CompanyDirection::B2B => CompanyDirection::B2C,
+        CompanyDirection::B2C => CompanyDirection::B2B
+      },
+      Some(Input::Character('c')) => setup.raise_cash(),
+      Some(Input::Character('C')) => setup.lower_cash(),
+      Some(Input::Character('r')) => {
+        let roles = EmployeeType::all();
+        role = roles[(roles.iter().position(|other| *other == role).unwrap_or(0) + 1) % roles.len()];
+      },
+      Some(Input::Character('t')) => setup.add_founder(role),
+      Some(Input::Character('T')) => setup.remove_founder(role),
+      Some(Input::Character('e')) => setup.global_economic_factors = setup::step_factor(setup.global_economic_factors, true),
+      Some(Input::Character('E')) => setup.global_economic_factors = setup::step_factor(setup.global_economic_factors, false),
+      Some(Input::Character('m')) => setup.competition_in_market = setup::step_factor(setup.competition_in_market, true),
+      Some(Input::Character('M')) => setup.competition_in_market = setup::step_factor(setup.competition_in_market, false),
+      Some(Input::Character('j')) => setup.job_market = setup::step_factor(setup.job_market, true),
+      Some(Input::Character('J')) => setup.job_market = setup::step_factor(setup.job_market, false),
+      _ => ()
+    }
+  }
+}
+
+// How long we wait for a key press before moving on to the next frame
+//
+const INPUT_POLL_MILLISECONDS: i32 = 20;
+
+fn main() {
+
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  if args.iter().any(|arg| arg == "--help") {
+    println!("{}", setup::USAGE);
+    return;
+  }
+
+  let setup = match GameSetup::from_args(&args) {
+    Ok(setup) => setup,
+    Err(error) => {
+      eprintln!("{}\n\n{}", error, setup::USAGE);
+      std::process::exit(1);
+    }
+  };
 
   // Init windows
   //
   let window = initscr();
 
-  draw_hud(&company, &software, &world, &window);
-  draw_matrix_workface(&company, &software, &world, &window);
+  start_color();
+  use_default_colors();
+  init_pair(1, COLOR_WHITE, -1);
+  init_pair(2, COLOR_GREEN, -1);
+  init_pair(3, COLOR_YELLOW, -1);
+  init_pair(4, COLOR_RED, -1);
+
+  let setup = if setup.quick_start { Some(setup) } else { run_setup_screen(setup, &window) };
+  let game_name = setup.as_ref().map(|setup| setup.name()).unwrap_or_default();
+  let (mut world, mut company, mut software) = match setup.map(|setup| setup.new_game()) {
+    Some(Ok(game)) => game,
+    Some(Err(error)) => {
+      endwin();
+      eprintln!("{}", error);
+      std::process::exit(1);
+    },
+    None => {
+      endwin();
+      return;
+    }
+  };
+
+  let (mut tabs, mut content, mut status) = match split_window(&window) {
+    Some(parts) => parts,
+    None => {
+      endwin();
+      eprintln!("The terminal is too small to play in");
+      std::process::exit(1);
+    }
+  };
 
   curs_set(0);
   window.refresh();
 
-  // set non-blocking mode
+  // set non-blocking mode - input is polled independently of the game clock
   //
-  window.timeout(world.speed() as i32);
+  window.timeout(INPUT_POLL_MILLISECONDS);
   window.keypad(true);
   noecho();
 
-  let command_string : String = String::new();
+  let mut navigator = Navigator::new();
+  let mut dialog = Dialog::new();
+  let mut notice: Option<String> = None;
+  let mut message_scroll: usize = 0;
+  let mut message_filter = Severity::Good;
+  let mut game_over = false;
+  let mut quitting = false;
+  let mut selected_team: usize = 0;
+  let mut roster = Roster::new();
+  let high_scores_path = HighScores::default_path();
+  let mut high_scores = HighScores::load(&high_scores_path).unwrap_or_default();
 
   // Store game time
   //
   loop {
+      let view = navigator.view();
+      let waiting = world.events().pending().first().map(|pending| (pending.definition().id, pending.raised()));
+
       match window.getch() {
+
+          // A decision on screen has to be dealt with before anything else
+          //
+          Some(Input::Character(c)) if dialog.is_open(waiting) => {
+            if let Some(choice) = c.to_digit(10).filter(|digit| *digit > 0) {
+              world.resolve_event(choice as usize - 1, &mut company, &mut software);
+            }
+
+            match c {
+              '\u{1b}' => dialog.dismiss(waiting),
+              ' ' => world.toggle_pause(),
+              _ => ()
+            }
+          }
+
           Some(Input::Character(c)) => {
               
-            // Check for escape
+            // Check for escape - it takes a second press to leave
             if c == '\u{1b}' {
-              //quitting = true;
-              //command_string = String::from("QUITTING");
-              break;
+              if quitting {
+                break;
+              }
+              quitting = true;
+              notice = Some("Press Esc again to quit, anything else to keep playing".to_string());
+            } else if quitting {
+              quitting = false;
+              notice = None;
+            }
+
+            if c == 'D' {
+              dialog.reopen();
             }
 
             if c == 'b' {
               company.cycle_direction();
             }
-            window.addch(c);
+
+            if c == 'u' {
+              company.cycle_upgrade_focus();
+            }
+
+            if c == 'w' {
+              let architecture = software.architecture().next();
+              world.rearchitect(&mut software, architecture);
+            }
+
+            // Recruit for whichever role we've picked
+            match c {
+              'H' => {
+                let role = company.hiring_role();
+                world.hire(&mut company, role);
+              },
+              'J' => company.cycle_hiring_role(),
+              _ => ()
+            }
+
+            // Extra hands for a crunch, freelance or from an agency
+            if c == 'k' {
+              world.engage_contractor(&mut company, Supplier::Freelance, EmployeeType::Developer);
+            }
+
+            if c == 'K' {
+              world.engage_contractor(&mut company, Supplier::Agency, EmployeeType::Developer);
+            }
+
+            // Where people work, and where we take them on
+            match c {
+              'W' => company.cycle_policy(),
+              'O' => world.open_office(&mut company),
+              'L' => company.cycle_hiring_region(),
+              _ => ()
+            }
+
+            if c == 'v' {
+              company.toggle_reviews();
+            }
+
+            if c == 'P' && view == View::OrgChart {
+              world.promote_from_within(&mut company);
+            }
+
+            // Game clock
+            match c {
+              ' ' => world.toggle_pause(),
+              '>' | '+' => world.faster(),
+              '<' | '-' => world.slower(),
+              'a' => world.toggle_auto_pause(),
+              'n' => world.advance_to_next_week(&mut company, &mut software),
+              'N' => world.advance_to_next_month(&mut company, &mut software),
+              'r' => world.raise_funding(&mut company, &software),
+              'i' => world.attempt_ipo(&mut company, &software),
+              _ => ()
+            }
+
+            if c == 'f' {
+              message_filter = message_filter.next();
+              message_scroll = 0;
+            }
+
+            // Team management only makes sense when we can see the teams
+            if view == View::Teams {
+              let team = company.teams().get(selected_team).map(|team| (team.id(), team.mission(), team.team_type()));
+              match (c, team) {
+                ('T', _) => { world.form_team(&mut company, TeamType::Agile, Mission::Product); },
+                ('X', Some((id, _, _))) => world.disband_team(&mut company, id),
+                ('M', Some((id, mission, _))) => company.set_team_mission(id, mission.next(), world.current_yearweek()),
+                ('Y', Some((id, _, team_type))) => company.set_team_type(id, team_type.next(), world.current_yearweek()),
+                ('A', Some((id, _, _))) => {
+                  if let Some(employee) = company.unassigned_employees().first().map(|employee| employee.id()) {
+                    world.assign_to_team(&mut company, employee, id);
+                  }
+                },
+                ('C', Some((id, _, _))) => {
+                  for member in company.team(id).map(|team| team.members().clone()).unwrap_or_default() {
+                    world.train(&mut company, member);
+                  }
+                },
+                ('[', _) => selected_team = selected_team.saturating_sub(1),
+                (']', _) => selected_team += 1,
+                _ => ()
+              }
+              selected_team = usize::min(selected_team, company.teams().len().saturating_sub(1));
+            }
+
+            // Working through the people on the roster
+            if view == View::Roster {
+              match c {
+                'S' => roster.next_sort(),
+                'R' => roster.reverse(),
+                'F' => roster.next_filter(),
+                'C' => {
+                  if let Some(id) = roster.selected_employee(&company).map(|employee| employee.id()) {
+                    world.train(&mut company, id);
+                  }
+                },
+                'E' => {
+                  if let Some(id) = roster.selected_employee(&company).map(|employee| employee.id()) {
+                    world.extend_contract(&mut company, id);
+                  }
+                },
+
+                // Move them on to the next executive or manager along
+                'B' => {
+                  if let Some((id, manager)) = roster.selected_employee(&company).map(|employee| (employee.id(), employee.manager())) {
+                    let mut managers: Vec<u16> = company.get_employees().values()
+                                                   .filter(|other| other.id() != id && (org::is_executive(other.employee_type()) || !company.reports(other.id()).is_empty()))
+                                                   .map(|other| other.id())
+                                                   .collect();
+                    managers.sort();
+                    let next = managers.iter().position(|other| Some(*other) == manager).map_or(0, |position| position + 1);
+                    if let Some(manager) = managers.get(next % managers.len().max(1)) {
+                      world.set_manager(&mut company, id, *manager);
+                    }
+                  }
+                },
+                '[' => roster.select_previous(),
+                ']' => roster.select_next(&company),
+                _ => ()
+              }
+
+              // The detail pane changes length from one person to the next
+              if "SRFCEB[]".contains(c) {
+                content.erase();
+              }
+            }
+
+            // Tabs go forward through the screens - the letters jump straight to a page
+            let page = match c {
+              '\t' => { navigator.next_screen(); None },
+              'p' => Some(View::Roster),
+              't' => Some(View::Teams),
+              'g' => Some(View::OrgChart),
+              'o' => Some(View::Scorecard),
+              'h' => Some(View::HighScores),
+              'c' => Some(View::Competitors),
+              'e' => Some(View::Economy),
+              'l' => Some(View::Events),
+              'm' => Some(View::Messages),
+              _ => None
+            };
+            if let Some(page) = page {
+              navigator.toggle(page);
+            }
 
           }
+          // Start again with parts that fit the new size - if they won't fit we carry on
+          // with the old ones until the terminal grows again
+          //
+          Some(Input::KeyResize) => {
+            resize_term(0, 0);
+            if let Some(parts) = split_window(&window) {
+              (tabs, content, status) = parts;
+            }
+            window.clear();
+            window.refresh();
+          },
+          Some(Input::KeyBTab) => navigator.previous_screen(),
+          Some(Input::KeyRight) => navigator.next_page(),
+          Some(Input::KeyLeft) => navigator.previous_page(),
+          Some(Input::KeyF1) => navigator.select_screen(Screen::Dashboard),
+          Some(Input::KeyF2) => navigator.select_screen(Screen::People),
+          Some(Input::KeyF3) => navigator.select_screen(Screen::Product),
+          Some(Input::KeyF4) => navigator.select_screen(Screen::Finance),
+          Some(Input::KeyF5) => navigator.select_screen(Screen::Market),
+          Some(Input::KeyF6) => navigator.select_screen(Screen::Events),
           Some(Input::KeyUp) => company.add_cash(1000),
           Some(Input::KeyDown) => company.remove_cash(1000),
           Some(Input::KeyDC) => break,
-          Some(input) => {
-              window.addstr(&format!("{:?}", input));
-          }
-          None => (),
+          Some(Input::KeyPPage) => message_scroll = message_scroll.saturating_sub(10),
+          Some(Input::KeyNPage) => message_scroll = usize::min(message_scroll + 10, world.messages().filtered(message_filter).len().saturating_sub(1)),
+          _ => ()
       }
 
-      // Format time and update
+      // Catch the game clock up and redraw every frame so pausing and key presses
+      // show straight away
       //
-      let format_time = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-      window.mvaddstr(0, window.get_max_x() - 20, format_time);
+      world.run_due_ticks(&mut company, &mut software, Local::now());
 
-      if Local::now() > world.last_tick_time() {
-        world.increment_game_ticks(&mut company, &mut software, Local::now());
+      // Once the game is over the scorecard is all that matters
+      //
+      if world.world_state() != WorldState::Nominal && !game_over {
+        game_over = true;
+        navigator.show(View::Scorecard);
 
-        draw_hud(&company, &software, &world, &window);
+        // A bad file shouldn't lose the game - the table just starts again
+        //
+        let position = high_scores.add(HighScore::from_game(&game_name, &world, &company, &software));
+        let _ = high_scores.save(&high_scores_path);
+        if let Some(position) = position {
+          notice = Some(format!("New high score - number {} on the table (h to view)", position + 1));
+        }
       }
 
-      // CMD prompt
+      // A new page starts from a clean slate
       //
-      let mut owned_string: String = "CMD> ".to_owned();
-      owned_string.push_str(&command_string);
+      if navigator.view() != view {
+        content.erase();
+      }
+
+      match navigator.view() {
+        View::Dashboard => {
+          draw_matrix_workface(&company, &software, &world, &content);
+          draw_hud(&company, &software, &world, &content);
+        },
+        View::Roster => draw_roster(&company, &content, &roster, world.current_yearweek()),
+        View::Teams => draw_teams(&company, &content, selected_team),
+        View::OrgChart => draw_org_chart(&company, &content),
+        View::Product => draw_product(&company, &software, &content),
+        View::Finance => draw_finance(&company, &software, &world, &content),
+        View::Scorecard => draw_scorecard(&company, &software, &world, &content),
+        View::HighScores => draw_high_scores(&high_scores, &content),
+        View::Competitors => draw_competitors(&world, &content),
+        View::Economy => draw_economy(&world, &content),
+        View::Events => draw_events(&world, &content),
+        View::Messages => draw_messages(&world, &content, message_scroll, message_filter)
+      }
+
+      draw_tabs(&navigator, &tabs);
+      draw_status_bar(&company, &software, &world, &status, notice.as_ref());
 
-      window.mvaddstr( window.get_max_y() - 1 , 0, owned_string);
-      window.refresh();
+      // Everything goes out together, with any decision on top
+      //
+      for part in [&tabs, &content, &status] {
+        part.touch();
+        part.noutrefresh();
+      }
+      let waiting = world.events().pending().first().map(|pending| (pending.definition().id, pending.raised()));
+      if dialog.is_open(waiting) {
+        if let Some(decision) = draw_decision(&world, &window) {
+          decision.noutrefresh();
+        }
+      }
+      doupdate();
   }
 
   endwin();
@@ -238,7 +1264,7 @@ mod test {
     #[test]
     fn world_tests() {
 
-      let world = World::new(100, 100, 100, 100, 0);
+      let (world, _company, _software) = GameSetup::preset(setup::Difficulty::Bootstrapped).new_game().unwrap();
       assert_eq!(world.game_ticks(), 0);
 
     }
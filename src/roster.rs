@@ -0,0 +1,195 @@
+use std::fmt;
+
+use crate::company::Company;
+use crate::employee::{Employee, EmployeeType};
+
+// What the roster is ordered by
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SortBy {
+    Name,
+    Role,
+    Age,
+    Salary,
+    Efficiency,
+    Talent,
+    Morale
+}
+
+impl SortBy {
+
+    pub fn next(&self) -> SortBy {
+        match self {
+            SortBy::Name => SortBy::Role,
+            SortBy::Role => SortBy::Age,
+            SortBy::Age => SortBy::Salary,
+            SortBy::Salary => SortBy::Efficiency,
+            SortBy::Efficiency => SortBy::Talent,
+            SortBy::Talent => SortBy::Morale,
+            SortBy::Morale => SortBy::Name
+        }
+    }
+}
+
+impl fmt::Display for SortBy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// Which people the roster screen is showing and who is picked out for the detail pane
+//
+pub struct Roster {
+    _sort_by: SortBy,
+    _descending: bool,
+    _filter: Option<EmployeeType>,      // just one role, or everyone
+    _selected: usize
+}
+
+impl Roster {
+
+    pub fn new() -> Roster {
+        Roster { _sort_by: SortBy::Name, _descending: false, _filter: None, _selected: 0 }
+    }
+
+    pub fn sort_by(&self) -> SortBy {
+        self._sort_by
+    }
+
+    pub fn descending(&self) -> bool {
+        self._descending
+    }
+
+    pub fn filter(&self) -> Option<EmployeeType> {
+        self._filter
+    }
+
+    pub fn selected(&self) -> usize {
+        self._selected
+    }
+
+    pub fn next_sort(&mut self) {
+        self._sort_by = self._sort_by.next();
+    }
+
+    pub fn reverse(&mut self) {
+        self._descending = !self._descending;
+    }
+
+    // Step through the roles, with everyone between the last and the first
+    //
+    pub fn next_filter(&mut self) {
+        let roles = EmployeeType::all();
+        self._filter = match self._filter {
+            None => Some(roles[0]),
+            Some(role) => roles.iter().skip_while(|other| **other != role).nth(1).copied()
+        };
+        self._selected = 0;
+    }
+
+    pub fn select_previous(&mut self) {
+        self._selected = self._selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self, company: &Company) {
+        self._selected = usize::min(self._selected + 1, self.rows(company).len().saturating_sub(1));
+    }
+
+    // Everyone who passes the filter, in order
+    //
+    pub fn rows<'a>(&self, company: &'a Company) -> Vec<&'a Employee> {
+        let mut rows: Vec<&Employee> = company.get_employees().values()
+                                              .filter(|employee| self._filter.is_none_or(|role| employee.employee_type() == role))
+                                              .collect();
+
+        // Ties go by name, then who joined first, so the order never jumps about
+        //
+        rows.sort_by_key(|employee| (employee.name(), employee.id()));
+        match self._sort_by {
+            SortBy::Name => (),
+            SortBy::Role => rows.sort_by_key(|employee| EmployeeType::all().iter().position(|role| *role == employee.employee_type())),
+            SortBy::Age => rows.sort_by_key(|employee| employee.age()),
+            SortBy::Salary => rows.sort_by_key(|employee| employee.salary()),
+            SortBy::Efficiency => rows.sort_by_key(|employee| employee.efficiency()),
+            SortBy::Talent => rows.sort_by_key(|employee| employee.talent()),
+            SortBy::Morale => rows.sort_by_key(|employee| employee.morale())
+        }
+
+        if self._descending {
+            rows.reverse();
+        }
+        rows
+    }
+
+    // Whoever the detail pane is showing - the last row if people have left since they were picked
+    //
+    pub fn selected_employee<'a>(&self, company: &'a Company) -> Option<&'a Employee> {
+        let rows = self.rows(company);
+        rows.get(usize::min(self._selected, rows.len().saturating_sub(1))).copied()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::company::CompanyDirection;
+
+    fn company() -> Company {
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        company.add_employee(Employee::new(EmployeeType::Developer, "Ada".to_string(), 36, 70, 100, 85));
+        company.add_employee(Employee::new(EmployeeType::Tester, "Brian".to_string(), 28, 50, 90, 70));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Charles".to_string(), 45, 90, 80, 95));
+        company.add_employee(Employee::new(EmployeeType::CTO, "Grace".to_string(), 52, 160, 90, 95));
+        company
+    }
+
+    fn names(roster: &Roster, company: &Company) -> Vec<String> {
+        roster.rows(company).iter().map(|employee| employee.name()).collect()
+    }
+
+    #[test]
+    fn roster_sort_test() {
+        let company = company();
+        let mut roster = Roster::new();
+        assert_eq!(names(&roster, &company), vec!["Ada", "Brian", "Charles", "Grace"]);
+
+        roster.next_sort();
+        assert_eq!(roster.sort_by(), SortBy::Role);
+        assert_eq!(names(&roster, &company), vec!["Grace", "Ada", "Charles", "Brian"]);
+
+        roster.next_sort();
+        roster.reverse();
+        assert_eq!(names(&roster, &company), vec!["Grace", "Charles", "Ada", "Brian"]);
+    }
+
+    #[test]
+    fn roster_filter_test() {
+        let company = company();
+        let mut roster = Roster::new();
+
+        roster.next_filter();
+        assert_eq!(roster.filter(), Some(EmployeeType::CEO));
+        assert!(roster.rows(&company).is_empty());
+        assert!(roster.selected_employee(&company).is_none());
+
+        // Developers come after the executives
+        for _role in 0..5 {
+            roster.next_filter();
+        }
+        assert_eq!(names(&roster, &company), vec!["Ada", "Charles"]);
+
+        roster.select_next(&company);
+        roster.select_next(&company);
+        assert_eq!(roster.selected_employee(&company).unwrap().name(), "Charles");
+
+        // Past the last role is everybody again
+        for _role in 0..8 {
+            roster.next_filter();
+        }
+        assert_eq!(roster.filter(), None);
+        assert_eq!(roster.selected(), 0);
+    }
+
+}
@@ -0,0 +1,322 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::company::{Company, CompanyDirection};
+use crate::company::workplace::{self, Policy, Region};
+use crate::employee::{Employee, EmployeeType};
+use crate::employee::skills::Skill;
+use crate::employee::names::NamePool;
+use crate::setup;
+use crate::software::Software;
+use crate::world::World;
+use crate::world::objectives::{Condition, Objectives};
+use crate::world::events::EVENTS;
+use crate::world::timeframe::{Timeframe, YearWeek};
+
+// Scenarios that ship with the game - file name and contents
+//
+const BUNDLED: [(&str, &str); 3] = [
+    ("dotcom_dash", include_str!("../scenarios/dotcom_dash.toml")),
+    ("legacy_rescue", include_str!("../scenarios/legacy_rescue.toml")),
+    ("recession_survivor", include_str!("../scenarios/recession_survivor.toml"))
+];
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldSpec {
+    pub start_year: i32,
+    pub start_week: u32,
+    pub global_economic_factors: u16,
+    pub competition_in_market: u16,
+    pub job_market: u16
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompanySpec {
+    pub cash: u32,
+    pub direction: CompanyDirection,
+    pub policy: Option<Policy>,
+    #[serde(default)]
+    pub offices: Vec<Region>            // as well as headquarters
+}
+
+// Anything left out is typical for the role. A count above one numbers the names.
+//
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmployeeSpec {
+    pub role: EmployeeType,
+    pub name: Option<String>,
+    #[serde(default = "one")]
+    pub count: usize,
+    pub age: Option<u16>,
+    pub compensation: Option<u32>,
+    pub efficiency: Option<u16>,
+    pub talent: Option<u16>,
+    #[serde(default)]
+    pub skills: HashMap<Skill, u16>,
+    pub region: Option<Region>          // headquarters otherwise
+}
+
+fn one() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SoftwareSpec {
+    pub lines_of_code: u32,
+    pub age_of_code: u16,
+    pub complexity_of_code: u16
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledEvent {
+    pub event: String,              // id of one of the events
+    pub year: i32,
+    pub week: u32
+}
+
+// A custom challenge - where we start, what happens along the way and how it's won or lost
+//
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    pub description: String,
+    pub world: WorldSpec,
+    pub company: CompanySpec,
+    #[serde(default)]
+    pub employees: Vec<EmployeeSpec>,
+    #[serde(default)]
+    pub software: SoftwareSpec,
+    #[serde(default)]
+    pub scheduled_events: Vec<ScheduledEvent>,
+    #[serde(default)]
+    pub win: Vec<Condition>,
+    #[serde(default)]
+    pub lose: Vec<Condition>,
+    pub names: Option<NamePool>         // who new hires might be - a built in pool otherwise
+}
+
+impl Scenario {
+
+    pub fn from_toml(text: &str) -> Result<Scenario, String> {
+        let scenario: Scenario = toml::from_str(text).map_err(|error| error.to_string())?;
+        scenario.validate()?;
+        return Ok(scenario)
+    }
+
+    pub fn load(path: &Path) -> Result<Scenario, String> {
+        let text = std::fs::read_to_string(path).map_err(|error| format!("Can't read {}: {}", path.display(), error))?;
+        Scenario::from_toml(&text).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    pub fn bundled() -> Vec<Scenario> {
+        BUNDLED.iter().map(|(_, text)| Scenario::from_toml(text).unwrap()).collect()
+    }
+
+    // A bundled scenario by file name, otherwise a path to a scenario file
+    //
+    pub fn find(name: &str) -> Result<Scenario, String> {
+        match BUNDLED.iter().find(|(bundled, _)| *bundled == name) {
+            Some((_, text)) => Scenario::from_toml(text),
+            None => Scenario::load(Path::new(name))
+        }
+    }
+
+    // The bundled scenario after this one - None wraps back round to a sandbox game
+    //
+    pub fn next_bundled(current: Option<&Scenario>) -> Option<Scenario> {
+        let bundled = Scenario::bundled();
+        let next = match current.and_then(|scenario| bundled.iter().position(|bundled| bundled.name == scenario.name)) {
+            Some(index) => index + 1,
+            None if current.is_some() => return None,
+            None => 0
+        };
+
+        bundled.into_iter().nth(next)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.start_yearweek()?;
+
+        for scheduled in &self.scheduled_events {
+            if !EVENTS.iter().any(|event| event.id == scheduled.event) {
+                return Err(format!("Unknown event '{}'", scheduled.event))
+            }
+            YearWeek::new(scheduled.year, scheduled.week).ok_or(format!("{} has no week {}", scheduled.year, scheduled.week))?;
+        }
+
+        for condition in self.win.iter().chain(self.lose.iter()) {
+            condition.validate()?;
+        }
+
+        if let Some(names) = &self.names {
+            names.validate()?;
+        }
+
+        // Everybody needs an office to go to unless the company is remote
+        //
+        if self.company.policy != Some(Policy::Remote) {
+            let offices: Vec<Region> = std::iter::once(workplace::HEADQUARTERS).chain(self.company.offices.iter().copied()).collect();
+            if let Some(region) = self.employees.iter().filter_map(|spec| spec.region).find(|region| !offices.contains(region)) {
+                return Err(format!("Nobody can be based in {} without an office there", region))
+            }
+        }
+
+        return Ok(())
+    }
+
+    pub fn start_yearweek(&self) -> Result<YearWeek, String> {
+        YearWeek::new(self.world.start_year, self.world.start_week).ok_or(format!("{} has no week {}", self.world.start_year, self.world.start_week))
+    }
+
+    fn employee_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+
+        for (index, spec) in self.employees.iter().enumerate() {
+            let base = spec.name.clone().unwrap_or(format!("{:?}", spec.role));
+            let earlier = self.employees[..index].iter().filter(|earlier| earlier.name.is_none() && spec.name.is_none() && earlier.role == spec.role)
+                                                        .map(|earlier| earlier.count).sum::<usize>();
+
+            for number in 1..=spec.count {
+                names.push(if spec.name.is_some() && spec.count == 1 { base.clone() } else { format!("{} {}", base, earlier + number) });
+            }
+        }
+
+        names
+    }
+
+    pub fn new_game(&self, speed: u16, ticks_per_week: u16, seed: u64) -> Result<(World, Company, Software), String> {
+        let timeframe = Timeframe::new(speed, 0, self.start_yearweek()?, ticks_per_week);
+        let mut world = World::new(self.world.global_economic_factors, self.world.competition_in_market, self.world.job_market, timeframe, seed);
+        world.set_objectives(Objectives::from_conditions(&self.win, &self.lose));
+        if let Some(names) = &self.names {
+            world.set_name_pool(names.clone());
+        }
+
+        for scheduled in &self.scheduled_events {
+            let yearweek = YearWeek::new(scheduled.year, scheduled.week).ok_or(format!("{} has no week {}", scheduled.year, scheduled.week))?;
+            if !world.schedule_event(&scheduled.event, yearweek) {
+                return Err(format!("Unknown event '{}'", scheduled.event))
+            }
+        }
+
+        let mut company = Company::new(self.company.cash, self.company.direction);
+        for region in &self.company.offices {
+            company.add_office(*region);
+        }
+
+        let specs = self.employees.iter().flat_map(|spec| std::iter::repeat_n(spec, spec.count));
+        for (spec, name) in specs.zip(self.employee_names()) {
+            let (age, compensation, efficiency, talent) = setup::typical_stats(spec.role);
+            let mut employee = Employee::new(spec.role, name,
+                                             spec.age.unwrap_or(age),
+                                             spec.compensation.unwrap_or(compensation),
+                                             spec.efficiency.unwrap_or(efficiency),
+                                             spec.talent.unwrap_or(talent));
+
+            let mut skills = *employee.skills();
+            skills.overrides(&spec.skills);
+            employee.set_skills(skills);
+            employee.set_region(spec.region.unwrap_or(workplace::HEADQUARTERS));
+            company.add_employee(employee);
+        }
+
+        // Everybody already here feels the policy, so it goes in once they have
+        //
+        if let Some(policy) = self.company.policy {
+            company.set_policy(policy);
+        }
+
+        let software = Software::new(self.software.lines_of_code, self.software.age_of_code, self.software.complexity_of_code, 0, self.start_yearweek()?);
+
+        return Ok((world, company, software))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::software::customers::Segment;
+    use crate::world::WorldState;
+
+    #[test]
+    fn bundled_scenarios_test() {
+        let bundled = Scenario::bundled();
+        assert_eq!(bundled.len(), BUNDLED.len());
+
+        let (world, company, software) = Scenario::find("legacy_rescue").unwrap().new_game(100, 2, 0).unwrap();
+        assert_eq!(world.game_year_week(), "2000-01");
+        assert_eq!(company.get_employees().len(), 7);
+        assert_eq!(company.employee_named("Grace").unwrap().age(), 52);
+        assert_eq!(company.employee_named("Grace").unwrap().skills().level(Skill::Security), 80);
+        assert!(company.employee_named("Developer 4").is_some());
+        assert_eq!(software.lines_of_code(), 80000);
+        let scorecard = world.scorecard(&company, &software);
+        assert_eq!(scorecard.entries[1].description, "2002-01 arrives");
+        assert_eq!(scorecard.score, 0);
+
+        let (_, company, _) = Scenario::find("recession_survivor").unwrap().new_game(100, 2, 0).unwrap();
+        assert_eq!(company.policy(), Policy::Hybrid);
+        assert_eq!(company.offices(), &vec![workplace::HEADQUARTERS, Region::Lisbon]);
+        assert_eq!(company.employee_named("Developer 3").unwrap().region(), Region::Lisbon);
+        assert_eq!(company.employee_named("Developer 3").unwrap().morale(), 70 + Policy::Hybrid.morale() as u16);
+    }
+
+    #[test]
+    fn dotcom_dash_test() {
+        let (mut world, mut company, mut software) = Scenario::find("dotcom_dash").unwrap().new_game(100, 2, 0).unwrap();
+        world.toggle_auto_pause();
+
+        // Keep three developers, some time on upgrades so releases keep coming, and sponsor
+        // the booth at the conference
+        company.cycle_upgrade_focus();
+        company.cycle_upgrade_focus();
+        while world.world_state() == WorldState::Nominal {
+            world.advance_to_next_week(&mut company, &mut software);
+            while !world.events().pending().is_empty() {
+                world.resolve_event(0, &mut company, &mut software);
+            }
+            while company.get_number_of_employees(EmployeeType::Developer) < 3 && world.hire(&mut company, EmployeeType::Developer).is_some() {}
+        }
+
+        assert_eq!(world.world_state(), WorldState::Won);
+        assert!(software.customer_base().segment_customers(Segment::Consumer) > 400);
+        assert!(world.current_yearweek() < YearWeek::new(2003, 1).unwrap());
+    }
+
+    #[test]
+    fn next_bundled_test() {
+        let first = Scenario::next_bundled(None).unwrap();
+        assert_eq!(first.name, "Dot-com Dash");
+
+        let second = Scenario::next_bundled(Some(&first)).unwrap();
+        assert_eq!(second.name, "Legacy Rescue");
+
+        let last = Scenario::next_bundled(Some(&second)).unwrap();
+        assert!(Scenario::next_bundled(Some(&last)).is_none());
+    }
+
+    #[test]
+    fn invalid_scenario_test() {
+        let scenario = |extra: &str| format!("name = \"Test\"\ndescription = \"Test\"\n\
+                                              [world]\nstart_year = 2000\nstart_week = 1\nglobal_economic_factors = 100\ncompetition_in_market = 100\njob_market = 100\n\
+                                              [company]\ncash = 100\ndirection = \"B2B\"\n{}", extra);
+
+        assert!(Scenario::from_toml(&scenario("")).is_ok());
+        assert!(Scenario::from_toml(&scenario("[[scheduled_events]]\nevent = \"alien_invasion\"\nyear = 2000\nweek = 2\n")).is_err());
+        assert!(Scenario::from_toml(&scenario("[[lose]]\ntype = \"date\"\nyear = 2005\nweek = 53\n")).is_err());
+        assert!(Scenario::from_toml(&scenario("[[employees]]\nrole = \"Wizard\"\n")).is_err());
+        assert!(Scenario::from_toml(&scenario("[names]\nfirst = []\nlast = [\"Lovelace\"]\n")).is_err());
+        assert!(Scenario::from_toml(&scenario("[[employees]]\nrole = \"Developer\"\nregion = \"Austin\"\n")).is_err());
+        assert!(Scenario::find("no_such_scenario.toml").is_err());
+    }
+
+}
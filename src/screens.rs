@@ -0,0 +1,220 @@
+use std::fmt;
+
+use crate::world::timeframe::YearWeek;
+
+// A page of the content area
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum View {
+    Dashboard,
+    Roster,
+    Teams,
+    OrgChart,
+    Product,
+    Finance,
+    Scorecard,
+    HighScores,
+    Competitors,
+    Economy,
+    Events,
+    Messages
+}
+
+impl fmt::Display for View {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            View::OrgChart => write!(f, "Org Chart"),
+            View::HighScores => write!(f, "High Scores"),
+            view => write!(f, "{:?}", view)
+        }
+    }
+}
+
+// The tabs across the top - each one is a handful of related pages
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Screen {
+    Dashboard,
+    People,
+    Product,
+    Finance,
+    Market,
+    Events
+}
+
+impl Screen {
+
+    pub fn all() -> [Screen; 6] {
+        [Screen::Dashboard, Screen::People, Screen::Product, Screen::Finance, Screen::Market, Screen::Events]
+    }
+
+    pub fn views(&self) -> &'static [View] {
+        match self {
+            Screen::Dashboard => &[View::Dashboard],
+            Screen::People => &[View::Roster, View::Teams, View::OrgChart],
+            Screen::Product => &[View::Product],
+            Screen::Finance => &[View::Finance, View::Scorecard, View::HighScores],
+            Screen::Market => &[View::Competitors, View::Economy],
+            Screen::Events => &[View::Events, View::Messages]
+        }
+    }
+
+    // The screen a page lives on
+    //
+    pub fn of(view: View) -> Screen {
+        Screen::all().into_iter().find(|screen| screen.views().contains(&view)).unwrap_or(Screen::Dashboard)
+    }
+
+    fn index(&self) -> usize {
+        Screen::all().iter().position(|screen| screen == self).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// Which screen is showing, and which page each screen was left on
+//
+pub struct Navigator {
+    _screen: Screen,
+    _pages: [usize; 6]
+}
+
+impl Navigator {
+
+    pub fn new() -> Navigator {
+        Navigator { _screen: Screen::Dashboard, _pages: [0; 6] }
+    }
+
+    pub fn screen(&self) -> Screen {
+        self._screen
+    }
+
+    pub fn view(&self) -> View {
+        self._screen.views()[self._pages[self._screen.index()]]
+    }
+
+    pub fn select_screen(&mut self, screen: Screen) {
+        self._screen = screen
+    }
+
+    pub fn next_screen(&mut self) {
+        let screens = Screen::all();
+        self._screen = screens[(self._screen.index() + 1) % screens.len()];
+    }
+
+    pub fn previous_screen(&mut self) {
+        let screens = Screen::all();
+        self._screen = screens[(self._screen.index() + screens.len() - 1) % screens.len()];
+    }
+
+    pub fn next_page(&mut self) {
+        let pages = self._screen.views().len();
+        let page = &mut self._pages[self._screen.index()];
+        *page = (*page + 1) % pages;
+    }
+
+    pub fn previous_page(&mut self) {
+        let pages = self._screen.views().len();
+        let page = &mut self._pages[self._screen.index()];
+        *page = (*page + pages - 1) % pages;
+    }
+
+    // Go to a page, on whichever screen it lives
+    //
+    pub fn show(&mut self, view: View) {
+        let screen = Screen::of(view);
+        self._screen = screen;
+        self._pages[screen.index()] = screen.views().iter().position(|other| *other == view).unwrap_or(0);
+    }
+
+    // Jump straight to a page - or back to the dashboard if it's already showing
+    //
+    pub fn toggle(&mut self, view: View) {
+        match self.view() == view {
+            true => self._screen = Screen::Dashboard,
+            false => self.show(view)
+        }
+    }
+}
+
+// The decision dialog - it stays up until the player decides or puts it away, and
+// comes back when something new needs deciding
+//
+pub struct Dialog {
+    _dismissed: Option<(&'static str, YearWeek)>    // the event that was put away
+}
+
+impl Dialog {
+
+    pub fn new() -> Dialog {
+        Dialog { _dismissed: None }
+    }
+
+    // Is the dialog up for the oldest event waiting on a decision?
+    //
+    pub fn is_open(&self, waiting: Option<(&'static str, YearWeek)>) -> bool {
+        waiting.is_some() && waiting != self._dismissed
+    }
+
+    pub fn dismiss(&mut self, waiting: Option<(&'static str, YearWeek)>) {
+        self._dismissed = waiting
+    }
+
+    pub fn reopen(&mut self) {
+        self._dismissed = None
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn navigator_test() {
+        let mut navigator = Navigator::new();
+        assert_eq!(navigator.view(), View::Dashboard);
+
+        navigator.next_screen();
+        assert_eq!(navigator.screen(), Screen::People);
+        navigator.previous_page();
+        assert_eq!(navigator.view(), View::OrgChart);
+
+        // Each screen remembers the page it was left on
+        navigator.previous_screen();
+        navigator.previous_screen();
+        assert_eq!(navigator.screen(), Screen::Events);
+        navigator.select_screen(Screen::People);
+        assert_eq!(navigator.view(), View::OrgChart);
+
+        // Jumping to a page, and jumping again goes home
+        navigator.toggle(View::Economy);
+        assert_eq!((navigator.screen(), navigator.view()), (Screen::Market, View::Economy));
+        navigator.toggle(View::Economy);
+        assert_eq!(navigator.view(), View::Dashboard);
+        navigator.next_page();
+        assert_eq!(navigator.view(), View::Dashboard);
+    }
+
+    #[test]
+    fn dialog_test() {
+        let raised = YearWeek::new(2000, 5).unwrap();
+        let mut dialog = Dialog::new();
+        assert!(!dialog.is_open(None));
+        assert!(dialog.is_open(Some(("data_breach", raised))));
+
+        // Put away until something else needs deciding
+        dialog.dismiss(Some(("data_breach", raised)));
+        assert!(!dialog.is_open(Some(("data_breach", raised))));
+        assert!(dialog.is_open(Some(("bug_bounty", raised))));
+
+        dialog.reopen();
+        assert!(dialog.is_open(Some(("data_breach", raised))));
+    }
+
+}
@@ -0,0 +1,441 @@
+use std::fmt;
+
+use crate::company::{Company, CompanyDirection};
+use crate::employee::{Employee, EmployeeType};
+use crate::scenario::Scenario;
+use crate::software::Software;
+use crate::world::World;
+use crate::world::objectives::{Condition, Objectives};
+use crate::world::timeframe::{Timeframe, YearWeek, DEFAULT_TICKS_PER_WEEK, START_YEARWEEK};
+
+pub const USAGE: &str = "Usage: cto-game [options]
+
+  --difficulty <preset>     bootstrapped, vc-backed or acquihire-rescue
+  --scenario <name|file>    dotcom_dash, legacy_rescue, recession_survivor or a TOML file
+  --start-year <year>       calendar year the game starts in
+  --start-week <week>       ISO week the game starts in
+  --cash <amount>           starting cash in the bank
+  --team <roles>            founding team e.g. developer:3,administrator
+  --direction <b2b|b2c>     who we sell to
+  --economy <0-1000>        global economic factors
+  --competition <0-1000>    competition in the market
+  --job-market <0-1000>     how easy it is to hire
+  --speed <1-1000>          milliseconds per tick at normal speed
+  --ticks-per-week <ticks>  game ticks in a week
+  --seed <number>           replay the same game
+  --quick                   skip the setup screen
+  --help                    show this message";
+
+// Starting cash the setup screen steps through
+//
+const CASH_STEPS: [u32; 9] = [50, 100, 250, 500, 1000, 2500, 5000, 10000, 25000];
+
+// How far one key press moves the economy, competition or job market
+//
+const MARKET_STEP: u16 = 50;
+
+// How hard the start of the game is
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Difficulty {
+    Bootstrapped,
+    VCBacked,
+    AcquihireRescue
+}
+
+impl Difficulty {
+
+    pub fn next(&self) -> Difficulty {
+        match self {
+            Difficulty::Bootstrapped => Difficulty::VCBacked,
+            Difficulty::VCBacked => Difficulty::AcquihireRescue,
+            Difficulty::AcquihireRescue => Difficulty::Bootstrapped
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        match name.to_lowercase().as_str() {
+            "bootstrapped" => Some(Difficulty::Bootstrapped),
+            "vc" | "vc-backed" => Some(Difficulty::VCBacked),
+            "acquihire" | "acquihire-rescue" => Some(Difficulty::AcquihireRescue),
+            _ => None
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Difficulty::Bootstrapped => "Three developers, an admin and not much money",
+            Difficulty::VCBacked => "A funded team in a crowded, well-funded market",
+            Difficulty::AcquihireRescue => "A big team and an old codebase with weeks of cash left"
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difficulty::Bootstrapped => write!(f, "Bootstrapped"),
+            Difficulty::VCBacked => write!(f, "VC-backed"),
+            Difficulty::AcquihireRescue => write!(f, "Acquihire rescue")
+        }
+    }
+}
+
+
+// Everything we need to start a new game
+//
+pub struct GameSetup {
+    pub difficulty: Difficulty,
+    pub start_year: i32,
+    pub start_week: u32,
+    pub cash: u32,
+    pub direction: CompanyDirection,
+    pub founding_team: Vec<EmployeeType>,
+    pub global_economic_factors: u16,       // 0 - 1000
+    pub competition_in_market: u16,         // 0 - 1000
+    pub job_market: u16,                    // 0 - 1000
+    pub lines_of_code: u32,                 // code we inherit
+    pub age_of_code: u16,
+    pub complexity_of_code: u16,
+    pub speed: u16,                         // milliseconds per tick at normal speed
+    pub ticks_per_week: u16,
+    pub seed: u64,                          // everything random in the game comes from here
+    pub goals: Vec<Condition>,              // all of these to win a sandbox game
+    pub quick_start: bool,                  // straight into the game without the setup screen
+    pub scenario: Option<Scenario>          // takes over from everything but the clock
+}
+
+impl GameSetup {
+
+    pub fn preset(difficulty: Difficulty) -> GameSetup {
+        let bootstrapped = GameSetup { difficulty,
+                                       start_year: START_YEARWEEK.year(),
+                                       start_week: START_YEARWEEK.week(),
+                                       cash: 100,
+                                       direction: CompanyDirection::B2B,
+                                       founding_team: vec![EmployeeType::Developer, EmployeeType::Developer, EmployeeType::Developer, EmployeeType::Administrator],
+                                       global_economic_factors: 100,
+                                       competition_in_market: 100,
+                                       job_market: 100,
+                                       lines_of_code: 0,
+                                       age_of_code: 0,
+                                       complexity_of_code: 0,
+                                       speed: 100,
+                                       ticks_per_week: DEFAULT_TICKS_PER_WEEK,
+                                       seed: rand::random(),
+                                       goals: vec![Condition::AnyOf { of: vec![Condition::ProfitableMonths { min: 12 }, Condition::Acquired] }],
+                                       quick_start: false,
+                                       scenario: None };
+
+        match difficulty {
+            Difficulty::Bootstrapped => bootstrapped,
+            Difficulty::VCBacked => GameSetup { cash: 5000,
+                                                founding_team: vec![EmployeeType::CEO, EmployeeType::CTO,
+                                                                    EmployeeType::Developer, EmployeeType::Developer, EmployeeType::Developer, EmployeeType::Developer,
+                                                                    EmployeeType::Tester, EmployeeType::Salesperson, EmployeeType::Marketeer],
+                                                global_economic_factors: 500,
+                                                competition_in_market: 400,
+                                                goals: vec![Condition::AnyOf { of: vec![Condition::Ipo, Condition::Acquired] }],
+                                                ..bootstrapped },
+            Difficulty::AcquihireRescue => GameSetup { cash: 50,
+                                                       founding_team: vec![EmployeeType::Developer, EmployeeType::Developer, EmployeeType::Developer,
+                                                                           EmployeeType::Developer, EmployeeType::Developer, EmployeeType::Developer,
+                                                                           EmployeeType::Tester, EmployeeType::Tester, EmployeeType::Administrator,
+                                                                           EmployeeType::ProductManager],
+                                                       competition_in_market: 300,
+                                                       lines_of_code: 50000,
+                                                       age_of_code: 150,
+                                                       complexity_of_code: 60,
+                                                       goals: vec![Condition::ProfitableMonths { min: 6 }],
+                                                       ..bootstrapped }
+        }
+    }
+
+    // Command line options on top of a preset - the difficulty is picked first so the
+    // other options override it wherever they appear
+    //
+    pub fn from_args(args: &[String]) -> Result<GameSetup, String> {
+        let mut setup = GameSetup::preset(Difficulty::Bootstrapped);
+
+        if let Some(position) = args.iter().position(|arg| arg == "--difficulty") {
+            let name = args.get(position + 1).ok_or("--difficulty needs a value")?;
+            setup = GameSetup::preset(Difficulty::from_name(name).ok_or(format!("Unknown difficulty '{}'", name))?);
+        }
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--quick" {
+                setup.quick_start = true;
+                continue;
+            }
+
+            let value = args.next().ok_or(format!("{} needs a value", arg))?;
+
+            match arg.as_str() {
+                "--difficulty" => (),
+                "--scenario" => setup.scenario = Some(Scenario::find(value)?),
+                "--start-year" => setup.start_year = parse_number(arg, value)?,
+                "--start-week" => setup.start_week = parse_number(arg, value)?,
+                "--cash" => setup.cash = parse_number(arg, value)?,
+                "--team" => setup.founding_team = parse_team(value)?,
+                "--direction" => setup.direction = match value.to_lowercase().as_str() {
+                    "b2b" => CompanyDirection::B2B,
+                    "b2c" => CompanyDirection::B2C,
+                    _ => return Err(format!("Unknown direction '{}'", value))
+                },
+                "--economy" => setup.global_economic_factors = u16::min(1000, parse_number(arg, value)?),
+                "--competition" => setup.competition_in_market = u16::min(1000, parse_number(arg, value)?),
+                "--job-market" => setup.job_market = u16::min(1000, parse_number(arg, value)?),
+                "--speed" => setup.speed = u16::clamp(parse_number(arg, value)?, 1, 1000),
+                "--ticks-per-week" => setup.ticks_per_week = u16::max(1, parse_number(arg, value)?),
+                "--seed" => setup.seed = parse_number(arg, value)?,
+                _ => return Err(format!("Unknown option '{}'", arg))
+            }
+        }
+
+        setup.start_yearweek()?;
+        return Ok(setup)
+    }
+
+    pub fn start_yearweek(&self) -> Result<YearWeek, String> {
+        YearWeek::new(self.start_year, self.start_week).ok_or(format!("{} has no week {}", self.start_year, self.start_week))
+    }
+
+    // Week 53 doesn't exist in every year so the week is pulled back to fit
+    //
+    pub fn set_start_year(&mut self, year: i32) {
+        self.start_year = year;
+        self.start_week = u32::min(self.start_week, YearWeek::weeks_in_year(year));
+    }
+
+    pub fn raise_cash(&mut self) {
+        self.cash = CASH_STEPS.iter().find(|step| **step > self.cash).copied().unwrap_or(self.cash);
+    }
+
+    pub fn lower_cash(&mut self) {
+        self.cash = CASH_STEPS.iter().rev().find(|step| **step < self.cash).copied().unwrap_or(self.cash);
+    }
+
+    pub fn add_founder(&mut self, employee_type: EmployeeType) {
+        self.founding_team.push(employee_type);
+    }
+
+    // Somebody has to start the company so the last founder stays
+    //
+    pub fn remove_founder(&mut self, employee_type: EmployeeType) {
+        if self.founding_team.len() > 1 {
+            if let Some(position) = self.founding_team.iter().rposition(|founder| *founder == employee_type) {
+                self.founding_team.remove(position);
+            }
+        }
+    }
+
+    // Pick another difficulty but keep the choices that aren't part of a preset
+    //
+    pub fn change_difficulty(&mut self, difficulty: Difficulty) {
+        let preset = GameSetup::preset(difficulty);
+        *self = GameSetup { start_year: self.start_year,
+                            start_week: self.start_week,
+                            direction: self.direction,
+                            speed: self.speed,
+                            ticks_per_week: self.ticks_per_week,
+                            seed: self.seed,
+                            quick_start: self.quick_start,
+                            ..preset };
+    }
+
+    // What the high-score table calls this game
+    //
+    pub fn name(&self) -> String {
+        match &self.scenario {
+            Some(scenario) => scenario.name.clone(),
+            None => format!("Sandbox ({})", self.difficulty)
+        }
+    }
+
+    pub fn new_game(&self) -> Result<(World, Company, Software), String> {
+        if let Some(scenario) = &self.scenario {
+            return scenario.new_game(self.speed, self.ticks_per_week, self.seed)
+        }
+
+        let timeframe = Timeframe::new(self.speed, 0, self.start_yearweek()?, self.ticks_per_week);
+        let mut world = World::new(self.global_economic_factors, self.competition_in_market, self.job_market, timeframe, self.seed);
+        world.set_objectives(Objectives::from_conditions(&self.goals, &[]));
+
+        let mut company = Company::new(self.cash, self.direction);
+        for (index, employee_type) in self.founding_team.iter().enumerate() {
+            let number = self.founding_team[..index].iter().filter(|earlier| *earlier == employee_type).count() + 1;
+            company.add_employee(founder(*employee_type, number));
+        }
+
+        let software = Software::new(self.lines_of_code, self.age_of_code, self.complexity_of_code, 0, self.start_yearweek()?);
+
+        return Ok((world, company, software))
+    }
+}
+
+// Age, compensation, efficiency and talent of someone typical in the role
+//
+pub fn typical_stats(employee_type: EmployeeType) -> (u16, u32, u16, u16) {
+    match employee_type {
+        EmployeeType::Developer => (30, 70, 100, 85),
+        EmployeeType::Tester => (28, 50, 90, 70),
+        EmployeeType::Salesperson => (35, 60, 90, 75),
+        EmployeeType::Marketeer => (32, 55, 90, 70),
+        EmployeeType::Administrator => (37, 80, 80, 65),
+        EmployeeType::Accountant => (40, 60, 90, 70),
+        EmployeeType::ProductOwner | EmployeeType::ProductManager => (35, 70, 95, 80),
+        EmployeeType::CEO | EmployeeType::CTO | EmployeeType::CMO | EmployeeType::CPO |
+        EmployeeType::FinanceDirector => (45, 150, 100, 90)
+    }
+}
+
+// A member of the founding team with typical numbers for their role
+//
+fn founder(employee_type: EmployeeType, number: usize) -> Employee {
+    let (age, compensation, efficiency, talent) = typical_stats(employee_type);
+    Employee::new(employee_type, format!("{:?} {}", employee_type, number), age, compensation, efficiency, talent)
+}
+
+// Move one of the 0 - 1000 market factors a step up or down
+//
+pub fn step_factor(factor: u16, up: bool) -> u16 {
+    match up {
+        true => u16::min(1000, factor + MARKET_STEP),
+        false => factor.saturating_sub(MARKET_STEP)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} expects a number, not '{}'", arg, value))
+}
+
+// developer:3,administrator - a role with an optional count
+//
+fn parse_team(value: &str) -> Result<Vec<EmployeeType>, String> {
+    let mut team = Vec::new();
+
+    for member in value.split(',') {
+        let (role, count) = match member.split_once(':') {
+            Some((role, count)) => (role, parse_number("--team", count)?),
+            None => (member, 1)
+        };
+
+        let employee_type = match role.trim().to_lowercase().as_str() {
+            "developer" | "dev" => EmployeeType::Developer,
+            "tester" => EmployeeType::Tester,
+            "salesperson" | "sales" => EmployeeType::Salesperson,
+            "marketeer" | "marketer" => EmployeeType::Marketeer,
+            "administrator" | "admin" => EmployeeType::Administrator,
+            "financedirector" => EmployeeType::FinanceDirector,
+            "ceo" => EmployeeType::CEO,
+            "cto" => EmployeeType::CTO,
+            "cmo" => EmployeeType::CMO,
+            "cpo" => EmployeeType::CPO,
+            "accountant" => EmployeeType::Accountant,
+            "productowner" => EmployeeType::ProductOwner,
+            "productmanager" => EmployeeType::ProductManager,
+            _ => return Err(format!("Unknown role '{}'", role))
+        };
+
+        team.extend(std::iter::repeat_n(employee_type, count));
+    }
+
+    return Ok(team)
+}
+
+// Founding team as "Developer x3, Administrator"
+//
+pub fn team_summary(team: &[EmployeeType]) -> String {
+    let mut counts: Vec<(EmployeeType, usize)> = Vec::new();
+
+    for employee_type in team {
+        match counts.iter_mut().find(|(counted, _)| counted == employee_type) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*employee_type, 1))
+        }
+    }
+
+    counts.iter()
+          .map(|(employee_type, count)| if *count > 1 { format!("{:?} x{}", employee_type, count) } else { format!("{:?}", employee_type) })
+          .collect::<Vec<String>>()
+          .join(", ")
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn preset_test() {
+        let setup = GameSetup::preset(Difficulty::VCBacked);
+        assert_eq!(setup.cash, 5000);
+        assert_eq!(team_summary(&setup.founding_team), "CEO, CTO, Developer x4, Tester, Salesperson, Marketeer");
+
+        let (world, company, software) = GameSetup::preset(Difficulty::AcquihireRescue).new_game().unwrap();
+        assert_eq!(company.get_employees().len(), 10);
+        assert_eq!(software.lines_of_code(), 50000);
+        assert_eq!(world.game_year_week(), "2000-01");
+    }
+
+    #[test]
+    fn from_args_test() {
+        let setup = GameSetup::from_args(&args("--cash 900 --difficulty vc --start-year 2004 --start-week 53 --team dev:2,admin --quick")).unwrap();
+        assert_eq!(setup.difficulty, Difficulty::VCBacked);
+        assert_eq!(setup.cash, 900);
+        assert_eq!(setup.founding_team, vec![EmployeeType::Developer, EmployeeType::Developer, EmployeeType::Administrator]);
+        assert!(setup.quick_start);
+
+        let (world, company, _software) = setup.new_game().unwrap();
+        assert_eq!(world.game_year_week(), "2004-53");
+        assert_eq!(company.cash_in_bank(), 900);
+        assert!(company.employee_named("Developer 2").is_some());
+    }
+
+    #[test]
+    fn from_args_error_test() {
+        assert!(GameSetup::from_args(&args("--start-year 2005 --start-week 53")).is_err());
+        assert!(GameSetup::from_args(&args("--difficulty impossible")).is_err());
+        assert!(GameSetup::from_args(&args("--cash lots")).is_err());
+        assert!(GameSetup::from_args(&args("--team wizard")).is_err());
+        assert!(GameSetup::from_args(&args("--cash")).is_err());
+        assert!(GameSetup::from_args(&args("--scenario moon_landing")).is_err());
+    }
+
+    #[test]
+    fn change_difficulty_test() {
+        let mut setup = GameSetup::preset(Difficulty::Bootstrapped);
+        setup.start_year = 2010;
+        setup.change_difficulty(setup.difficulty.next());
+
+        assert_eq!(setup.difficulty, Difficulty::VCBacked);
+        assert_eq!(setup.start_year, 2010);
+        assert_eq!(setup.cash, 5000);
+
+        // Cash, team and market can all be changed on the setup screen
+        setup.raise_cash();
+        assert_eq!(setup.cash, 10000);
+        setup.cash = 7;
+        setup.lower_cash();
+        assert_eq!(setup.cash, 7);
+        setup.founding_team = vec![EmployeeType::Developer];
+        setup.add_founder(EmployeeType::Tester);
+        setup.remove_founder(EmployeeType::Developer);
+        setup.remove_founder(EmployeeType::Tester);
+        assert_eq!(setup.founding_team, vec![EmployeeType::Tester]);
+        assert_eq!(step_factor(980, true), 1000);
+        assert_eq!(step_factor(20, false), 0);
+
+        setup.set_start_year(2004);
+        setup.start_week = 53;
+        setup.set_start_year(2005);
+        assert_eq!(setup.start_yearweek().unwrap().to_string(), "2005-52");
+    }
+
+}
@@ -1,11 +1,77 @@
 use rand::Rng;
+use std::fmt;
 use crate::world::timeframe::YearWeek;
+
+pub mod customers;
+
+use customers::{CustomerBase, Segment, ServiceLevels};
+
+// On average each dependency ships a release we fall behind on every six months
+//
+const DEPENDENCY_RELEASE_WEEKS: u16 = 26;
+
+// Lines of code that typically pull in another third party dependency
+//
+const LINES_PER_DEPENDENCY: u32 = 5000;
+
+// Lines of code behind each point of feature richness
+//
+const LINES_PER_FEATURE_POINT: u32 = 2000;
+
+// Dev days it takes to bring one outdated dependency up to date
+//
+const DEV_DAYS_PER_UPGRADE: u32 = 2;
+
+// Technical debt taken on moving the codebase to a new architecture
+//
+const REARCHITECTURE_DEBT: u16 = 20;
+
+// Dev days a migration takes - a fixed amount of scaffolding plus a share for every
+// line of code that has to move across
+//
+const MIGRATION_BASE_DAYS: u32 = 30;
+const LINES_PER_MIGRATION_DAY: u32 = 1000;
+
+// New code written since the last release before it's worth shipping another
+//
+const LINES_PER_RELEASE: u32 = 10000;
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Architecture {
-    ProofofConcept /* ,
+    ProofofConcept,
     Monolith,
     Microservices,
-    EventDriven */
+    EventDriven
+}
+
+impl Architecture {
+
+    pub fn next(&self) -> Architecture {
+        match self {
+            Architecture::ProofofConcept => Architecture::Monolith,
+            Architecture::Monolith => Architecture::Microservices,
+            Architecture::Microservices => Architecture::EventDriven,
+            Architecture::EventDriven => Architecture::ProofofConcept
+        }
+    }
+
+    // How far teams can get on without waiting on each other (0 - 100). Everyone is in
+    // everybody else's way in a proof of concept.
+    //
+    pub fn team_independence(&self) -> u16 {
+        match self {
+            Architecture::ProofofConcept => 10,
+            Architecture::Monolith => 40,
+            Architecture::Microservices => 80,
+            Architecture::EventDriven => 90
+        }
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
 }
 
 pub enum MonetizationModel {
@@ -25,16 +91,19 @@ pub struct Software {
     _components:            u16,    // components 1 - 100
     _services:              u16,    // services 1 - 100
     _dependencies:          u16,    // dependencies 1 - 100
+    _outdated_dependencies: u16,    // dependencies behind their latest release
+    _dependency_drift:      u16,    // dependency-weeks accumulated towards the next upstream release
     _cost_of_service:       u16,    // price of service
     _architecture: Architecture,    // what is the predominant architecture
-    _customer_satisfaction: u16,    // 0 - 100
-    _customers: u16,                // number of customers
-    _active_users: u32,             // number of active users
+    _migrating_to: Option<Architecture>,    // the architecture we're moving to, if any
+    _migration_days:        u32,    // dev days left before the migration is done
+    _customer_base: CustomerBase,   // accounts by segment
     _capacity_percentage_active_users: u16,             // number of active users (not same as customers)
     _percentage_free_users: u16,    // percentage of free users
     _monetization_model:    MonetizationModel,
     _releases:              u16,    // how many releases have their been
     _last_release_yearweek: YearWeek,
+    _released_lines:        u32,    // lines of code in the last release
     _reliability:           u16,    // 0 - 100
     _technical_debt:        u16,    // 0 - 100
     _quality:               u16
@@ -45,25 +114,38 @@ pub struct Software {
 //
 impl Software {
 
-    pub fn new(lines_of_code: u32, age_of_code: u16, complexity_of_code: u16, cost_of_service: u16) -> Software {   
+    pub fn new(lines_of_code: u32, age_of_code: u16, complexity_of_code: u16, cost_of_service: u16, start_yearweek: YearWeek) -> Software {   
+
+        // Code we inherit comes with the dependencies it pulled in - the older it is the
+        // further behind they have fallen
+        //
+        let dependencies = match lines_of_code {
+            0 => 0,
+            lines => u32::min(100, 1 + lines / LINES_PER_DEPENDENCY) as u16
+        };
+        let outdated_dependencies = u32::min(dependencies as u32, dependencies as u32 * age_of_code as u32 / DEPENDENCY_RELEASE_WEEKS as u32) as u16;
+
         return Software { _lines_of_code: lines_of_code,
                           _age_of_code: age_of_code,
                           _complexity_of_code: complexity_of_code,
-                          _feature_richness: 0,
+                          _feature_richness: u32::min(100, lines_of_code / LINES_PER_FEATURE_POINT) as u16,
                           _ease_of_use: 0,
                           _components: 0,
                           _services: 0,
-                          _dependencies: 0,
+                          _dependencies: dependencies,
+                          _outdated_dependencies: outdated_dependencies,
+                          _dependency_drift: 0,
                           _cost_of_service: cost_of_service,
                           _architecture: Architecture::ProofofConcept,
-                          _customer_satisfaction: 0,
-                          _customers: 0,
-                          _active_users: 0,
+                          _migrating_to: None,
+                          _migration_days: 0,
+                          _customer_base: CustomerBase::new(),
                           _capacity_percentage_active_users: 0,            // 0 - 100 percentage to capacity - but can be over capacity too
                           _percentage_free_users: 0,
                           _monetization_model: MonetizationModel::Proprietary,
                           _releases: 0,
-                          _last_release_yearweek: YearWeek::new(2000, 1),
+                          _last_release_yearweek: start_yearweek,
+                          _released_lines: 0,
                           _reliability: 100,                               // 0 - 100 - as reported by users - not testers
                           _technical_debt: 0,                              // 0 - 100 - dependent upon development and new feature pace
                           _quality: 100                                    // 0 - 100 - as measured by testers
@@ -116,10 +198,10 @@ impl Software {
             return 0
         }
 
-        let usability = ( self._ease_of_use + self._feature_richness ) / 2 - self._technical_debt;
+        let usability = ( ( self._ease_of_use + self._feature_richness ) / 2 ).saturating_sub(self._technical_debt);
 
         if usability > 0 {
-            return usability as u16
+            return usability
         } else  {
             return 0
         }
@@ -134,7 +216,7 @@ impl Software {
             return 0
         }
 
-        let mut popularity = ( self._customers as i16 + self._capacity_percentage_active_users as i16 ) / 2;
+        let mut popularity = ( u32::min(self.customers(), 100) as i16 + self._capacity_percentage_active_users as i16 ) / 2;
         
         // Now when was the last release?
         //
@@ -164,45 +246,213 @@ impl Software {
         self._releases
     }
 
+    // Enough new work has piled up since the last release to be worth shipping
+    //
+    pub fn ready_for_release(&self) -> bool {
+        self._lines_of_code >= self._released_lines + LINES_PER_RELEASE
+    }
+
+    pub fn release(&mut self, yearweek: YearWeek) {
+        self._releases += 1;
+        self._last_release_yearweek = yearweek;
+        self._released_lines = self._lines_of_code;
+    }
+
     pub fn reliability(&self) -> u16 {
         self._reliability
     }
 
-    pub fn add_customers(&mut self, customers: u16, b2b: bool) {
-        self._customers += customers;
+    pub fn customer_base(&self) -> &CustomerBase {
+        &self._customer_base
+    }
 
-        // Adjust users according to b2b selling
-        //
-        if b2b {
-            let mut rng = rand::thread_rng();
-            let rand_factor: f32 = rng.gen();
-            let rand_users: f32 = rng.gen();
-    
-            self._active_users += ( customers as f32 * rand_factor * rand_users * 20.0f32 ) as u32
+    pub fn technical_debt(&self) -> u16 {
+        self._technical_debt
+    }
 
-        } else {
-            self._active_users += customers as u32;
+    pub fn add_technical_debt(&mut self, technical_debt: u16) {
+        self._technical_debt = u16::min(100, self._technical_debt + technical_debt);
+    }
+
+    pub fn architecture(&self) -> Architecture {
+        self._architecture
+    }
+
+    pub fn migrating_to(&self) -> Option<Architecture> {
+        self._migrating_to
+    }
+
+    pub fn migration_days(&self) -> u32 {
+        self._migration_days
+    }
+
+    // Start moving to a new architecture - the bigger the codebase the longer it takes and
+    // we keep running on the old one until it's done. Returns false if one is already underway.
+    //
+    pub fn rearchitect(&mut self, architecture: Architecture) -> bool {
+        if self._migrating_to.is_some() || architecture == self._architecture {
+            return false;
+        }
+        self._migrating_to = Some(architecture);
+        self._migration_days = MIGRATION_BASE_DAYS + self._lines_of_code / LINES_PER_MIGRATION_DAY;
+        return true;
+    }
+
+    // Work on the migration - once the last of it is done we switch over and the mess it
+    // leaves behind becomes technical debt. Returns true when the migration completes.
+    //
+    pub fn work_on_migration(&mut self, number_of_devs: u16, dev_focus: u16, days: u16) -> bool {
+        let architecture = match self._migrating_to {
+            Some(architecture) => architecture,
+            None => return false
+        };
+
+        let dev_days = ( number_of_devs as f32 * (dev_focus as f32 / 100.0f32 ) * days as f32 ) as u32;
+        self._migration_days = self._migration_days.saturating_sub(dev_days);
+        if self._migration_days > 0 {
+            return false;
         }
+
+        self._architecture = architecture;
+        self._migrating_to = None;
+        self.add_technical_debt(REARCHITECTURE_DEBT);
+        return true;
+    }
+
+    pub fn adjust_reliability(&mut self, change: i16) {
+        self._reliability = i16::clamp(self._reliability as i16 + change, 0, 100) as u16;
     }
 
-    pub fn remove_customers(&mut self, customers: u16) {
+    pub fn adjust_customer_satisfaction(&mut self, change: i16) {
+        self._customer_base.adjust_satisfaction(change);
+    }
+
+    // Businesses sign accounts across the segments - mostly small ones.
+    // Consumers sign up in cohorts, bigger ones when marketing is behind them.
+    //
+    pub fn add_customers<R: Rng>(&mut self, b2b: bool, marketing: u32, rng: &mut R) {
+        if b2b {
+            let rand_segment: u16 = rng.gen_range(0, 100);
+
+            let segment = if rand_segment < 70 {
+                Segment::SMB
+            } else if rand_segment < 95 {
+                Segment::MidMarket
+            } else {
+                Segment::Enterprise
+            };
 
-        if customers > self._customers {
-            // remove a percentage of active users
-            //
-            let factor = customers as f64 / self._customers as f64;
-            self._active_users /= ( self._active_users as f64 * factor) as u32;
-            self._customers -= customers
+            self._customer_base.sign_up_business(segment, rng);
         } else {
-            self._customers = 0;
-            self._active_users = 0;
+            self._customer_base.sign_up_consumers(marketing);
         }
     }
 
+    // A new account of a typical size for its segment
+    //
+    pub fn add_account<R: Rng>(&mut self, segment: Segment, rng: &mut R) {
+        self._customer_base.sign_up_business(segment, rng);
+    }
+
+    // A won B2B deal becomes an account at its negotiated value
+    //
+    pub fn add_contract(&mut self, segment: Segment, seats: u32, contract_value: u32) {
+        self._customer_base.sign_up_contract(segment, seats, contract_value);
+    }
+
+    // How much support work our customers generate - every business account needs
+    // looking after while consumers mostly help themselves
+    //
+    pub fn support_load(&self) -> u32 {
+        let consumers = self._customer_base.segment_customers(Segment::Consumer);
+        self._customer_base.customers() - consumers + consumers / 100
+    }
+
+    // Weekly update of customer satisfaction followed by churn. Returns customers lost.
+    //
+    pub fn update_customers<R: Rng>(&mut self, support_responsiveness: u16, competitor_price: u16, rng: &mut R) -> u32 {
+        let service_levels = ServiceLevels { reliability: self._reliability,
+                                             quality: self._quality,
+                                             feature_richness: self._feature_richness,
+                                             support_responsiveness,
+                                             competitor_price };
+
+        self._customer_base.update_week(&service_levels, rng)
+    }
+
+    pub fn lose_to_competitor(&mut self) -> u32 {
+        self._customer_base.lose_to_competitor()
+    }
+
+    pub fn roll_customer_month(&mut self) {
+        self._customer_base.roll_month();
+    }
+
+    pub fn customer_satisfaction(&self) -> u16 {
+        self._customer_base.satisfaction()
+    }
+
+    pub fn churn_rate(&self) -> f32 {
+        self._customer_base.churn_rate()
+    }
+
+    pub fn feature_richness(&self) -> u16 {
+        self._feature_richness
+    }
+
     pub fn age_of_code(&self) -> u16 {
         self._age_of_code
     }
 
+    pub fn dependencies(&self) -> u16 {
+        self._dependencies
+    }
+
+    pub fn outdated_dependencies(&self) -> u16 {
+        self._outdated_dependencies
+    }
+
+    // Age the codebase by a week - upstream keeps releasing whether we keep up or not
+    //
+    pub fn age_code(&mut self) {
+        self._age_of_code = self._age_of_code.saturating_add(1);
+
+        self._dependency_drift += self._dependencies;
+        let newly_outdated = self._dependency_drift / DEPENDENCY_RELEASE_WEEKS;
+        self._dependency_drift %= DEPENDENCY_RELEASE_WEEKS;
+
+        self._outdated_dependencies = u16::min(self._dependencies, self._outdated_dependencies + newly_outdated);
+
+        // Known vulnerabilities in old dependencies start to hurt in production
+        //
+        if self.security_risk() > 50 {
+            self._reliability = self._reliability.saturating_sub(1);
+        }
+    }
+
+    // Everything brought up to date in one go
+    //
+    pub fn upgrade_all_dependencies(&mut self) {
+        self._outdated_dependencies = 0;
+    }
+
+    // Percentage of our dependencies which are out of date (0 - 100)
+    //
+    pub fn security_risk(&self) -> u16 {
+        if self._dependencies == 0 {
+            return 0
+        }
+
+        ( self._outdated_dependencies as u32 * 100 / self._dependencies as u32 ) as u16
+    }
+
+    // Outdated dependencies slow everybody down - up to half of capacity is lost
+    // to workarounds, incompatible docs and pinned versions
+    //
+    pub fn apply_upgrade_drag(&self, dev_capacity: u16) -> u16 {
+        dev_capacity - ( dev_capacity as u32 * self.security_risk() as u32 / 200 ) as u16
+    }
+
     pub fn complexity_of_code(&self) -> u16 {
         self._complexity_of_code
     }
@@ -212,9 +462,10 @@ impl Software {
     }
 
 
-    // Three key methods
+    // Four key methods
     //
     // - Work on Features
+    // - Work on Upgrades
     // - Refactor
     // - Bug Fix
     //
@@ -227,6 +478,27 @@ impl Software {
         self._lines_of_code += ( number_of_devs as f32 * (dev_focus as f32 / 100.0f32 ) ) as u32 * loc_per_day * days as u32;
 
         self.recalculate_code_complexity(number_of_devs, dev_focus);
+
+        // Features are roughly proportional to the code we have written
+        //
+        self._feature_richness = u32::min(100, self._lines_of_code / LINES_PER_FEATURE_POINT) as u16;
+
+        // More code pulls in more dependencies - these arrive up to date
+        //
+        let dependencies = u32::min(100, 1 + self._lines_of_code / LINES_PER_DEPENDENCY) as u16;
+        if dependencies > self._dependencies {
+            self._dependencies = dependencies;
+        }
+    }
+
+    // Work on upgrades
+    //
+    // Spend dev time bringing outdated dependencies back up to date
+    pub fn work_on_upgrades(&mut self, number_of_devs: u16, dev_focus: u16, days: u16) {
+        let dev_days = ( number_of_devs as f32 * (dev_focus as f32 / 100.0f32 ) ) as u32 * days as u32;
+        let upgraded = u32::min(self._outdated_dependencies as u32, dev_days / DEV_DAYS_PER_UPGRADE) as u16;
+
+        self._outdated_dependencies -= upgraded;
     }
     
     // Try and keep dev_focus 0 - 100
@@ -249,8 +521,16 @@ impl Software {
      }
 
 
-    pub fn customers(&self) -> u16 {
-        self._customers
+    pub fn customers(&self) -> u32 {
+        self._customer_base.customers()
+    }
+
+    pub fn active_users(&self) -> u64 {
+        self._customer_base.active_users()
+    }
+
+    pub fn monthly_recurring_revenue(&self) -> u32 {
+        self._customer_base.monthly_recurring_revenue()
     }
 
 
@@ -261,13 +541,99 @@ impl Software {
 mod test {
 
     use super::*;
+    use crate::world::timeframe::START_YEARWEEK;
 
     #[test]
     fn software_customers_tests() {
-        let mut software = Software::new(0, 0, 0, 0);
+        let mut software = Software::new(0, 0, 0, 0, START_YEARWEEK);
+
+        software.add_customers(false, 0, &mut rand::thread_rng());
+        software.add_customers(true, 0, &mut rand::thread_rng());
+        assert_eq!(software.customers(), 2);
+        assert_eq!(software.customer_base().segment_customers(Segment::Consumer), 1);
+
+        assert_eq!(software.support_load(), 1);
+        assert_eq!(software.customer_satisfaction(), 50);
+    }
 
-        software.add_customers(20, false);
-        assert_eq!(software.customers(), 20);
+    #[test]
+    fn software_dependency_ageing_tests() {
+        let mut software = Software::new(0, 0, 0, 0, START_YEARWEEK);
+
+        software.work_on_features(2, 100, 50);
+        assert_eq!(software.dependencies(), 6);
+        assert_eq!(software.security_risk(), 0);
+
+        for _week in 0..26 {
+            software.age_code();
+        }
+        assert_eq!(software.age_of_code(), 26);
+        assert_eq!(software.outdated_dependencies(), 6);
+        assert_eq!(software.security_risk(), 100);
+        assert_eq!(software.apply_upgrade_drag(100), 50);
+
+        // An inherited codebase has fallen behind already
+        let software = Software::new(50000, 13, 60, 0, START_YEARWEEK);
+        assert_eq!(software.dependencies(), 11);
+        assert_eq!(software.outdated_dependencies(), 5);
+    }
+
+    #[test]
+    fn software_upgrade_tests() {
+        let mut software = Software::new(0, 0, 0, 0, START_YEARWEEK);
+
+        software.work_on_features(2, 100, 50);
+        for _week in 0..13 {
+            software.age_code();
+        }
+        assert_eq!(software.outdated_dependencies(), 3);
+
+        software.work_on_upgrades(2, 50, 2);
+        assert_eq!(software.outdated_dependencies(), 2);
+
+        software.work_on_upgrades(4, 100, 10);
+        assert_eq!(software.outdated_dependencies(), 0);
+    }
+
+    #[test]
+    fn software_architecture_tests() {
+        let mut software = Software::new(0, 0, 0, 0, START_YEARWEEK);
+        assert_eq!(software.architecture(), Architecture::ProofofConcept);
+
+        // Nothing changes until the migration has been worked through
+        assert!(software.rearchitect(Architecture::Microservices));
+        assert!(!software.rearchitect(Architecture::Monolith));
+        assert_eq!(software.migration_days(), MIGRATION_BASE_DAYS);
+        assert!(!software.work_on_migration(2, 100, 10));
+        assert_eq!(software.architecture(), Architecture::ProofofConcept);
+        assert_eq!(software.technical_debt(), 0);
+
+        assert!(software.work_on_migration(2, 100, 5));
+        assert_eq!(software.architecture(), Architecture::Microservices);
+        assert_eq!(software.migrating_to(), None);
+        assert_eq!(software.technical_debt(), REARCHITECTURE_DEBT);
+
+        assert!(!software.rearchitect(Architecture::Microservices));
+        assert!(!software.work_on_migration(2, 100, 5));
+        assert_eq!(software.technical_debt(), REARCHITECTURE_DEBT);
+        assert!(Architecture::EventDriven.team_independence() > Architecture::Monolith.team_independence());
+    }
+
+    #[test]
+    fn software_release_tests() {
+        let mut software = Software::new(0, 0, 0, 0, START_YEARWEEK);
+        assert!(!software.ready_for_release());
+        assert_eq!(software.usability_factor(), 0);
+
+        software.work_on_features(2, 100, 20);
+        assert!(software.ready_for_release());
+
+        let yearweek = YearWeek::new(2000, 10).unwrap();
+        software.release(yearweek);
+        assert_eq!(software.releases(), 1);
+        assert!(!software.ready_for_release());
+        assert!(software.usability_factor() > 0);
+        assert_eq!(software.market_popularity(&yearweek.add_weeks(1)), 15);
     }
 
 
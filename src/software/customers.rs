@@ -0,0 +1,523 @@
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+
+// Who buys our software
+//
+// Business segments sign individual accounts with a number of seats. Consumers
+// arrive in cohorts - one account holds all the users who signed up together so
+// that we can grow to millions of users without tracking every one of them.
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Segment {
+    SMB,
+    MidMarket,
+    Enterprise,
+    Consumer
+}
+
+impl Segment {
+
+    pub fn all() -> [Segment; 4] {
+        [Segment::SMB, Segment::MidMarket, Segment::Enterprise, Segment::Consumer]
+    }
+
+    // Monthly list price for a seat - bigger deals get volume discounts
+    //
+    pub fn list_price_per_seat(&self) -> u32 {
+        match self {
+            Segment::SMB => 10,
+            Segment::MidMarket => 8,
+            Segment::Enterprise => 6,
+            Segment::Consumer => 1
+        }
+    }
+
+    // Chance in a thousand that a lukewarm account leaves in any given week
+    //
+    pub fn base_churn_risk(&self) -> u16 {
+        match self {
+            Segment::SMB => 8,
+            Segment::MidMarket => 5,
+            Segment::Enterprise => 2,
+            Segment::Consumer => 15
+        }
+    }
+
+    // Range of seats a new business account is likely to sign for
+    //
+    pub fn typical_seats(&self) -> (u32, u32) {
+        match self {
+            Segment::SMB => (5, 20),
+            Segment::MidMarket => (50, 200),
+            Segment::Enterprise => (500, 5000),
+            Segment::Consumer => (1, 1)
+        }
+    }
+
+    // How feature rich the product needs to be before it fits this segment (0 - 100)
+    //
+    pub fn feature_requirement(&self) -> u16 {
+        match self {
+            Segment::SMB => 30,
+            Segment::MidMarket => 50,
+            Segment::Enterprise => 80,
+            Segment::Consumer => 20
+        }
+    }
+
+    // How much each driver counts towards satisfaction - each row adds up to 100
+    //
+    //                     reliability quality features price support
+    fn satisfaction_weights(&self) -> [u16; 5] {
+        match self {
+            Segment::SMB =>        [20,        15,     25,      30,   10],
+            Segment::MidMarket =>  [25,        20,     25,      15,   15],
+            Segment::Enterprise => [30,        15,     20,       5,   30],
+            Segment::Consumer =>   [20,        30,     20,      25,    5]
+        }
+    }
+
+    pub fn is_business(&self) -> bool {
+        *self != Segment::Consumer
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+// Why did a customer leave us?
+//
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum ChurnReason {
+    Reliability,
+    Quality,
+    FeatureFit,
+    Price,
+    Support,
+    Competitor
+}
+
+impl ChurnReason {
+    pub fn all() -> [ChurnReason; 6] {
+        [ChurnReason::Reliability, ChurnReason::Quality, ChurnReason::FeatureFit, ChurnReason::Price, ChurnReason::Support, ChurnReason::Competitor]
+    }
+}
+
+impl fmt::Display for ChurnReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// What we are currently delivering to every customer (all 0 - 100)
+//
+#[derive(Debug, Copy, Clone)]
+pub struct ServiceLevels {
+    pub reliability: u16,
+    pub quality: u16,
+    pub feature_richness: u16,
+    pub support_responsiveness: u16,
+    pub competitor_price: u16       // what the competition charges as a percentage of our list price
+}
+
+
+pub struct Account {
+    _id: u32,
+    _segment: Segment,
+    _seats: u32,                // users on this account (consumer cohort size)
+    _contract_value: u32,       // per month
+    _satisfaction: u16,         // 0 - 100
+    _usage: u16,                // 0 - 100 percentage of seats actively used
+    _churn_risk: u16,           // chance in a thousand of leaving in any given week
+    _weakest_driver: ChurnReason // what they would complain about most
+}
+
+impl Account {
+
+    pub fn new(id: u32, segment: Segment, seats: u32) -> Account {
+        Account { _id: id,
+                  _segment: segment,
+                  _seats: seats,
+                  _contract_value: seats * segment.list_price_per_seat(),
+                  _satisfaction: 50,
+                  _usage: 50,
+                  _churn_risk: segment.base_churn_risk(),
+                  _weakest_driver: ChurnReason::FeatureFit }
+    }
+
+    #[allow(dead_code)]
+    pub fn id(&self) -> u32 {
+        self._id
+    }
+
+    pub fn segment(&self) -> Segment {
+        self._segment
+    }
+
+    pub fn seats(&self) -> u32 {
+        self._seats
+    }
+
+    pub fn contract_value(&self) -> u32 {
+        self._contract_value
+    }
+
+    pub fn satisfaction(&self) -> u16 {
+        self._satisfaction
+    }
+
+    pub fn churn_risk(&self) -> u16 {
+        self._churn_risk
+    }
+
+    pub fn weakest_driver(&self) -> ChurnReason {
+        self._weakest_driver
+    }
+
+    // Score each driver of satisfaction for this account (0 - 100)
+    //
+    fn driver_scores(&self, service_levels: &ServiceLevels) -> [(ChurnReason, u16); 5] {
+        let feature_fit = u32::min(100, service_levels.feature_richness as u32 * 100 / self._segment.feature_requirement() as u32) as u16;
+
+        // What they'd pay elsewhere for the same seats against what they pay us
+        //
+        let elsewhere = self._seats as u64 * self._segment.list_price_per_seat() as u64 * service_levels.competitor_price as u64 / 100;
+        let price = match elsewhere.checked_mul(100).and_then(|elsewhere| elsewhere.checked_div(self._contract_value as u64)) {
+            Some(price) => u64::min(100, price) as u16,
+            None => 100
+        };
+
+        [(ChurnReason::Reliability, service_levels.reliability),
+         (ChurnReason::Quality, service_levels.quality),
+         (ChurnReason::FeatureFit, feature_fit),
+         (ChurnReason::Price, price),
+         (ChurnReason::Support, service_levels.support_responsiveness)]
+    }
+
+    // Satisfaction moves a quarter of the way towards what we deliver each week, usage
+    // follows satisfaction and unhappy accounts become more likely to leave
+    //
+    pub fn update_satisfaction(&mut self, service_levels: &ServiceLevels) {
+        let scores = self.driver_scores(service_levels);
+        let weights = self._segment.satisfaction_weights();
+
+        let mut target: u32 = 0;
+        for (index, (_reason, score)) in scores.iter().enumerate() {
+            target += u16::min(100, *score) as u32 * weights[index] as u32;
+        }
+        let target = ( target / 100 ) as u16;
+
+        self._satisfaction = ( self._satisfaction * 3 + target ) / 4;
+        self._usage = ( self._usage * 3 + self._satisfaction ) / 4;
+        self._churn_risk = ( self._segment.base_churn_risk() as u32 * ( 100 - self._satisfaction ) as u32 / 50 ) as u16;
+
+        if let Some((reason, _score)) = scores.iter().min_by_key(|(_reason, score)| *score) {
+            self._weakest_driver = *reason;
+        }
+    }
+
+    pub fn adjust_satisfaction(&mut self, change: i16) {
+        self._satisfaction = i16::clamp(self._satisfaction as i16 + change, 0, 100) as u16;
+    }
+
+    // A business account is one customer, a consumer cohort is one customer per seat
+    //
+    pub fn customers(&self) -> u32 {
+        if self._segment.is_business() {
+            return 1
+        }
+
+        self._seats
+    }
+
+    pub fn active_users(&self) -> u64 {
+        self._seats as u64 * self._usage as u64 / 100
+    }
+
+    // Drop some seats - the contract value shrinks with them
+    //
+    fn remove_seats(&mut self, seats: u32) {
+        let seats = u32::min(seats, self._seats);
+        self._contract_value -= u32::min(self._contract_value, seats * self._segment.list_price_per_seat());
+        self._seats -= seats;
+    }
+}
+
+
+pub struct CustomerBase {
+    _accounts: Vec<Account>,
+    _next_id: u32,
+    _customers_at_month_start: u32,
+    _lost_this_month: u32,
+    _churn_rate: f32,                               // percentage of customers lost last month
+    _churn_reasons: HashMap<ChurnReason, u32>       // customers lost for each reason since we started
+}
+
+impl CustomerBase {
+
+    pub fn new() -> CustomerBase {
+        CustomerBase { _accounts: Vec::new(),
+                       _next_id: 1,
+                       _customers_at_month_start: 0,
+                       _lost_this_month: 0,
+                       _churn_rate: 0.0f32,
+                       _churn_reasons: HashMap::new() }
+    }
+
+    pub fn accounts(&self) -> &Vec<Account> {
+        &self._accounts
+    }
+
+    // Sign up a new account and return its id
+    //
+    pub fn sign_up(&mut self, segment: Segment, seats: u32) -> u32 {
+        self.sign_up_contract(segment, seats, seats * segment.list_price_per_seat())
+    }
+
+    // Sign up an account at a negotiated monthly contract value
+    //
+    pub fn sign_up_contract(&mut self, segment: Segment, seats: u32, contract_value: u32) -> u32 {
+        let id = self._next_id;
+        self._next_id += 1;
+
+        let mut account = Account::new(id, segment, seats);
+        account._contract_value = contract_value;
+        self._accounts.push(account);
+        return id
+    }
+
+    // Sign up a business account with a typical number of seats for its segment
+    //
+    pub fn sign_up_business<R: Rng>(&mut self, segment: Segment, rng: &mut R) -> u32 {
+        let (min_seats, max_seats) = segment.typical_seats();
+        let seats = rng.gen_range(min_seats, max_seats + 1);
+
+        self.sign_up(segment, seats)
+    }
+
+    // Consumers grow by word of mouth so a cohort is proportional to the users we already have,
+    // plus whoever marketing brought along
+    //
+    pub fn sign_up_consumers(&mut self, marketing: u32) -> u32 {
+        let cohort = u32::max(1, self.segment_customers(Segment::Consumer) / 20 + marketing);
+        self.sign_up(Segment::Consumer, cohort)
+    }
+
+    // Weekly update of every account followed by churn. Business accounts leave on a
+    // roll of their churn risk while consumer cohorts shed their expected share of users -
+    // with whatever fraction of a user is left over leaving on a roll too.
+    //
+    // Returns the number of customers lost.
+    //
+    pub fn update_week<R: Rng>(&mut self, service_levels: &ServiceLevels, rng: &mut R) -> u32 {
+        let mut lost = 0;
+
+        for account in self._accounts.iter_mut() {
+            account.update_satisfaction(service_levels);
+
+            let account_lost = if account.segment().is_business() {
+                if rng.gen_range(0, 1000) < account.churn_risk() {
+                    account.remove_seats(account.seats());
+                    1
+                } else {
+                    0
+                }
+            } else {
+                let expected = account.seats() as u64 * account.churn_risk() as u64;
+                let mut leaving = ( expected / 1000 ) as u32;
+                if rng.gen_range(0, 1000) < expected % 1000 {
+                    leaving += 1;
+                }
+                account.remove_seats(leaving);
+                leaving
+            };
+
+            if account_lost > 0 {
+                *self._churn_reasons.entry(account.weakest_driver()).or_insert(0) += account_lost;
+                lost += account_lost;
+            }
+        }
+
+        self._accounts.retain(|account| account.seats() > 0);
+        self._lost_this_month += lost;
+        lost
+    }
+
+    // Something happened that every customer noticed
+    //
+    pub fn adjust_satisfaction(&mut self, change: i16) {
+        for account in self._accounts.iter_mut() {
+            account.adjust_satisfaction(change);
+        }
+    }
+
+    // A competitor wins over our least happy business account, or a slice of our least
+    // happy consumer cohort. Returns the number of customers lost.
+    //
+    pub fn lose_to_competitor(&mut self) -> u32 {
+        let least_happy = self._accounts.iter().enumerate().min_by_key(|(_index, account)| account.satisfaction()).map(|(index, _account)| index);
+
+        let lost = match least_happy {
+            None => return 0,
+            Some(index) => {
+                let account = &mut self._accounts[index];
+                let lost = if account.segment().is_business() { account.seats() } else { account.seats() / 20 + 1 };
+
+                account.remove_seats(lost);
+                let lost = if account.segment().is_business() { 1 } else { lost };
+
+                if account.seats() == 0 {
+                    self._accounts.remove(index);
+                }
+                lost
+            }
+        };
+
+        *self._churn_reasons.entry(ChurnReason::Competitor).or_insert(0) += lost;
+        self._lost_this_month += lost;
+        lost
+    }
+
+    // Close off the month - work out the churn rate and start counting again
+    //
+    pub fn roll_month(&mut self) {
+        if self._customers_at_month_start > 0 {
+            self._churn_rate = self._lost_this_month as f32 * 100.0f32 / self._customers_at_month_start as f32;
+        } else {
+            self._churn_rate = 0.0f32;
+        }
+
+        self._customers_at_month_start = self.customers();
+        self._lost_this_month = 0;
+    }
+
+    pub fn churn_rate(&self) -> f32 {
+        self._churn_rate
+    }
+
+    pub fn churn_reasons(&self) -> &HashMap<ChurnReason, u32> {
+        &self._churn_reasons
+    }
+
+    // Average satisfaction across all customers
+    //
+    pub fn satisfaction(&self) -> u16 {
+        let customers = self.customers() as u64;
+        if customers == 0 {
+            return 0
+        }
+
+        let total: u64 = self._accounts.iter().map(|account| account.satisfaction() as u64 * account.customers() as u64).sum();
+        ( total / customers ) as u16
+    }
+
+    pub fn customers(&self) -> u32 {
+        self._accounts.iter().map(|account| account.customers()).sum()
+    }
+
+    pub fn segment_customers(&self, segment: Segment) -> u32 {
+        self._accounts.iter().filter(|account| account.segment() == segment).map(|account| account.customers()).sum()
+    }
+
+    pub fn active_users(&self) -> u64 {
+        self._accounts.iter().map(|account| account.active_users()).sum()
+    }
+
+    pub fn monthly_recurring_revenue(&self) -> u32 {
+        self._accounts.iter().map(|account| account.contract_value()).sum()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn customer_base_sign_up_tests() {
+        let mut customer_base = CustomerBase::new();
+
+        customer_base.sign_up(Segment::Enterprise, 1000);
+        customer_base.sign_up(Segment::SMB, 10);
+        customer_base.sign_up(Segment::Consumer, 2000000);
+
+        assert_eq!(customer_base.customers(), 2000002);
+        assert_eq!(customer_base.segment_customers(Segment::Enterprise), 1);
+        assert_eq!(customer_base.active_users(), 1000505);
+        assert_eq!(customer_base.monthly_recurring_revenue(), 6000 + 100 + 2000000);
+    }
+
+    #[test]
+    fn account_satisfaction_tests() {
+        let mut account = Account::new(1, Segment::Enterprise, 1000);
+        let service_levels = ServiceLevels { reliability: 100, quality: 100, feature_richness: 40, support_responsiveness: 100, competitor_price: 100 };
+
+        account.update_satisfaction(&service_levels);
+        assert_eq!(account.satisfaction(), 60);
+        assert_eq!(account.weakest_driver(), ChurnReason::FeatureFit);
+        assert_eq!(account.churn_risk(), 1);
+    }
+
+    #[test]
+    fn account_price_tests() {
+        let service_levels = ServiceLevels { reliability: 100, quality: 100, feature_richness: 100, support_responsiveness: 100, competitor_price: 80 };
+
+        // Paying list price when the competition is cheaper
+        let account = Account::new(1, Segment::SMB, 10);
+        assert_eq!(account.driver_scores(&service_levels)[3], (ChurnReason::Price, 80));
+
+        // A discount on the deal more than makes up for it
+        let mut account = Account::new(2, Segment::SMB, 10);
+        account._contract_value = 70;
+        assert_eq!(account.driver_scores(&service_levels)[3], (ChurnReason::Price, 100));
+    }
+
+    #[test]
+    fn customer_base_churn_tests() {
+        let mut customer_base = CustomerBase::new();
+        let service_levels = ServiceLevels { reliability: 0, quality: 100, feature_richness: 100, support_responsiveness: 100, competitor_price: 100 };
+
+        customer_base.sign_up(Segment::Consumer, 10000);
+        customer_base.roll_month();
+
+        // Satisfaction only creeps up to 57 so risk is 15 * 43 / 50 = 12 in a thousand
+        assert_eq!(customer_base.update_week(&service_levels, &mut rand::thread_rng()), 120);
+        assert_eq!(customer_base.customers(), 9880);
+        assert_eq!(customer_base.churn_reasons()[&ChurnReason::Reliability], 120);
+
+        customer_base.roll_month();
+        assert_eq!(customer_base.churn_rate(), 1.2f32);
+
+        // A small cohort still loses somebody now and then - a zero roll always does
+        let mut customer_base = CustomerBase::new();
+        customer_base.sign_up(Segment::Consumer, 20);
+        assert_eq!(customer_base.update_week(&service_levels, &mut StepRng::new(0, 0)), 1);
+        assert_eq!(customer_base.customers(), 19);
+    }
+
+    #[test]
+    fn customer_base_competitor_tests() {
+        let mut customer_base = CustomerBase::new();
+
+        customer_base.sign_up(Segment::MidMarket, 100);
+        customer_base.sign_up(Segment::Consumer, 1000);
+
+        let service_levels = ServiceLevels { reliability: 100, quality: 100, feature_richness: 0, support_responsiveness: 100, competitor_price: 100 };
+        for account in customer_base._accounts.iter_mut() {
+            account.update_satisfaction(&service_levels);
+        }
+
+        // Mid-market needs more features so they're the least happy
+        assert_eq!(customer_base.lose_to_competitor(), 1);
+        assert_eq!(customer_base.lose_to_competitor(), 51);
+        assert_eq!(customer_base.churn_reasons()[&ChurnReason::Competitor], 52);
+    }
+
+}
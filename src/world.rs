@@ -1,52 +1,301 @@
 use chrono::{DateTime};
 use chrono::{Local};
-use rand::Rng;
-use crate::employee::{EmployeeType};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::fmt;
+use crate::employee::{Employee, EmployeeType};
+use crate::employee::skills::Skills;
+use crate::employee::career::{self, Rating};
+use crate::employee::names::NamePool;
+use crate::employee::contract::{Contract, Supplier};
+use crate::setup;
 
 pub mod timeframe;
+pub mod competitor;
+pub mod economy;
+pub mod events;
+pub mod messages;
+pub mod objectives;
+pub mod achievements;
+pub mod score;
 
 use super::Company;
+use crate::company::{CompanyDirection, Ownership};
+use crate::company::team::{Mission, TeamType};
+use crate::company::sales::SalesConditions;
 use super::Software;
-use timeframe::Timeframe;
+use crate::software::Architecture;
+use timeframe::{GameSpeed, Rollover, Timeframe};
+use competitor::{Competitor, CompetitorAction};
+use economy::Economy;
+use events::{Effect, EventContext, Events};
+use economy::EconomicPhase;
+use messages::{Category, MessageBus, Severity};
+use objectives::{ObjectiveContext, Objectives, Scorecard, IPO_MIN_VALUATION};
+use timeframe::YearWeek;
+use achievements::{AchievementContext, Achievements};
+use score::{Score, ScoreContext};
+
+// Percentage chance each week of a new competitor entering the market when
+// funding is at its most available
+//
+const NEW_ENTRANT_CHANCE: u16 = 2;
+
+// Weeks investors want to see between funding rounds
+//
+const FUNDING_ROUND_WEEKS: u32 = 13;
+
+// Profitable months in a row the markets want before an IPO
+//
+const IPO_PROFITABLE_MONTHS: u16 = 6;
+
+// New hires come in short of somebody typical for the role - how short depends on
+// luck and on how many good people are looking
+//
+const HIRE_MIN_EFFICIENCY: u16 = 60;
+const HIRE_EFFICIENCY_SPREAD: u16 = 30;
+const HIRE_TALENT_SPREAD: u16 = 20;
+
+// Finding somebody costs a recruiter a few months of their pay, and there have to be
+// enough people looking for work to find anyone at all
+//
+const RECRUITMENT_FEE_MONTHS: u32 = 3;
+const MIN_HIRING_POOL: u16 = 50;
+
+// Names for new teams - once they're used up teams just get numbers
+//
+const TEAM_NAMES: [&str; 8] = ["Thundercats", "Avengers", "Ghostbusters", "Goonies", "A-Team", "Wombles", "Jets", "Sharks"];
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum WorldState {
     Nominal,
-    Bust
+    Bust,
+    Won,
+    Lost,
+    Acquired                              // sold up - the game is over whatever the objectives say
+}
+
+impl fmt::Display for WorldState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
 }
 
 // World for our Software and Company to live in
 //
 pub struct World {
-    _global_economic_factors: u16,        // 0-1000
-    _competition_in_market: u16,          // 0-1000
+    _economy: Economy,                    // global economic factors and the cycle
+    _competitors: Vec<Competitor>,        // who we're up against
     _job_market: u16,                     // 0-1000
     _timeframe: Timeframe,                // See class
+    _events: Events,                      // things that happen to us and what we decided
+    _messages: MessageBus,                // what the simulation has to tell the player
+    _auto_pause: bool,                    // stop the clock when something important happens
+    _objectives: Objectives,              // how the game is won or lost
+    _profitable_months: u16,              // months in a row where we made more than we paid out
+    _recessions_survived: u16,            // recessions we've come out the other side of
+    _achievements: Achievements,          // milestones reached so far
+    _names: NamePool,                     // where new hires' names come from
+    _last_funding_round: Option<YearWeek>,
+    _seed: u64,                           // everything random in the game comes from here
+    _rng: StdRng,
     _world_state: WorldState              // How are we coping?
 }
 
 impl World {
 
-    pub fn new(global_economic_factors :u16, competition_in_market :u16, job_market :u16, speed :u16, game_ticks :u32) -> World {   
-        return World { _global_economic_factors: global_economic_factors, _competition_in_market: competition_in_market, _job_market: job_market, _timeframe: Timeframe::new(speed, game_ticks), _world_state: WorldState::Nominal };
+    pub fn new(global_economic_factors :u16, competition_in_market :u16, job_market :u16, timeframe :Timeframe, seed :u64) -> World {   
+        let mut rng = StdRng::seed_from_u64(seed);
+        let competitors = competitor::generate_competitors(competition_in_market, &mut rng);
+        return World { _economy: Economy::new(global_economic_factors), _competitors: competitors, _job_market: job_market, _timeframe: timeframe, _events: Events::new(), _messages: MessageBus::new(), _auto_pause: true, _objectives: Objectives::new(), _profitable_months: 0, _recessions_survived: 0, _achievements: Achievements::new(), _names: NamePool::default(), _last_funding_round: None, _seed: seed, _rng: rng, _world_state: WorldState::Nominal };
     }
 
     pub fn global_economic_factors(& self) -> u16 {
-        self._global_economic_factors
+        self._economy.index()
     }
 
+    pub fn economy(&self) -> &Economy {
+        &self._economy
+    }
+
+    // Derived from the competitors in the market (0-1000)
+    //
     pub fn competition_in_market(&self) -> u16 {
-        self._competition_in_market
+        competitor::competition_in_market(&self._competitors)
+    }
+
+    pub fn competitors(&self) -> &Vec<Competitor> {
+        &self._competitors
     }
 
     pub fn job_market(&self) -> u16 {
         self._job_market
     }
 
+    // How much of the job market we can hire from - it grows the less we ask people to
+    // come into an office
+    //
+    pub fn hiring_pool(&self, company: &Company) -> u16 {
+        ( self._job_market as u32 * company.policy().hiring_reach() as u32 / 100 ) as u16
+    }
+
+    pub fn events(&self) -> &Events {
+        &self._events
+    }
+
+    pub fn messages(&self) -> &MessageBus {
+        &self._messages
+    }
+
+    pub fn seed(&self) -> u64 {
+        self._seed
+    }
+
+    pub fn world_state(&self) -> WorldState {
+        self._world_state
+    }
+
+    pub fn set_objectives(&mut self, objectives: Objectives) {
+        self._objectives = objectives;
+    }
+
+    pub fn profitable_months(&self) -> u16 {
+        self._profitable_months
+    }
+
+    pub fn weeks_played(&self) -> u32 {
+        self._timeframe.get_start_yearweek().difference_weeks(&self._timeframe.get_current_yearweek())
+    }
+
+    pub fn achievements(&self) -> &Achievements {
+        &self._achievements
+    }
+
+    // How well the game has gone - can be asked for at any time but really means
+    // something once it's over
+    //
+    pub fn score(&self, company: &Company, software: &Software) -> Score {
+        Score::calculate(&ScoreContext { valuation: self.diluted_valuation(company, software),
+                                         weeks_played: self.weeks_played(),
+                                         morale: company.average_morale(),
+                                         customer_satisfaction: software.customer_satisfaction(),
+                                         achievement_points: self._achievements.points(),
+                                         won: self._world_state == WorldState::Won })
+    }
+
+    // What the company is worth - a multiple of annual revenue that rises and falls
+    // with the economy, plus whatever is in the bank
+    //
+    pub fn valuation(&self, company: &Company, software: &Software) -> u32 {
+        let multiple = 1 + self._economy.index() as u32 / 100;
+        software.monthly_recurring_revenue().saturating_mul(12).saturating_mul(multiple).saturating_add(company.cash_in_bank())
+    }
+
+    // What the company is worth once everybody's share options come out of it
+    //
+    pub fn diluted_valuation(&self, company: &Company, software: &Software) -> u32 {
+        let retained = career::OPTION_POOL - u32::min(career::OPTION_POOL, company.options_granted());
+        ( self.valuation(company, software) as u64 * retained as u64 / career::OPTION_POOL as u64 ) as u32
+    }
+
+    pub fn objective_context(&self, company: &Company, software: &Software) -> ObjectiveContext {
+        ObjectiveContext { customers: software.customers(),
+                           cash: company.cash_in_bank(),
+                           monthly_revenue: software.monthly_recurring_revenue(),
+                           employees: company.get_employees().len() as u16,
+                           funding_raised: company.funding_raised(),
+                           valuation: self.valuation(company, software),
+                           profitable_months: self._profitable_months,
+                           ownership: company.ownership(),
+                           start_yearweek: self._timeframe.get_start_yearweek(),
+                           yearweek: self._timeframe.get_current_yearweek() }
+    }
+
+    pub fn scorecard(&self, company: &Company, software: &Software) -> Scorecard {
+        self._objectives.scorecard(&self.objective_context(company, software))
+    }
+
+    // Book one of the events in for a given week - false if there's no such event
+    //
+    pub fn schedule_event(&mut self, id: &str, yearweek: YearWeek) -> bool {
+        self._events.schedule(id, yearweek)
+    }
+
+    fn emit(&mut self, severity: Severity, category: Category, text: String) {
+        self._messages.emit(self._timeframe.get_current_yearweek(), severity, category, text);
+    }
+
+    #[allow(dead_code)]
     pub fn speed(&self) -> u16 {
         self._timeframe.speed()
     }
 
+    pub fn game_speed(&self) -> GameSpeed {
+        self._timeframe.game_speed()
+    }
+
+    pub fn faster(&mut self) {
+        self._timeframe.set_game_speed(self._timeframe.game_speed().faster());
+    }
+
+    pub fn slower(&mut self) {
+        self._timeframe.set_game_speed(self._timeframe.game_speed().slower());
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self._timeframe.is_paused()
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self._timeframe.set_paused(!self._timeframe.is_paused(), Local::now());
+    }
+
+    pub fn auto_pause(&self) -> bool {
+        self._auto_pause
+    }
+
+    pub fn toggle_auto_pause(&mut self) {
+        self._auto_pause = !self._auto_pause;
+    }
+
+    // Run however many ticks are owed since the last frame. Returns the ticks run.
+    //
+    pub fn run_due_ticks(&mut self, company: &mut Company, software: &mut Software, time_now: DateTime<Local>) -> u32 {
+        let due = self._timeframe.ticks_due(time_now);
+
+        for tick in 0..due {
+            self.increment_game_ticks(company, software, time_now);
+
+            if self.is_paused() || self._world_state != WorldState::Nominal {
+                return tick + 1
+            }
+        }
+
+        return due
+    }
+
+    // Skip straight to the start of the next week or month and pause there
+    //
+    pub fn advance_to_next_week(&mut self, company: &mut Company, software: &mut Software) {
+        let yearweek = self._timeframe.get_current_yearweek();
+        self.advance_until(company, software, |world| world._timeframe.get_current_yearweek() != yearweek);
+    }
+
+    pub fn advance_to_next_month(&mut self, company: &mut Company, software: &mut Software) {
+        let month = self.game_month();
+        self.advance_until(company, software, |world| world.game_month() != month);
+    }
+
+    fn advance_until(&mut self, company: &mut Company, software: &mut Software, done: impl Fn(&World) -> bool) {
+        self._timeframe.set_paused(false, Local::now());
+
+        while !done(self) && !self.is_paused() && self._world_state == WorldState::Nominal {
+            self.increment_game_ticks(company, software, Local::now());
+        }
+
+        self._timeframe.set_paused(true, Local::now());
+    }
+
     pub fn game_year(&self) -> i32 {
         self._timeframe.get_current_year()
     }
@@ -59,6 +308,10 @@ impl World {
         self._timeframe.get_current_month()
     }
 
+    pub fn current_yearweek(&self) -> YearWeek {
+        self._timeframe.get_current_yearweek()
+    }
+
     pub fn game_year_week(&self) -> String {
         self._timeframe.get_current_yearweek().to_string()
     }
@@ -67,6 +320,7 @@ impl World {
         self._timeframe.game_ticks()
     }
 
+    #[allow(dead_code)]
     pub fn last_tick_time(&self) -> DateTime<Local> {
         self._timeframe.last_tick_time()
     }
@@ -88,11 +342,17 @@ impl World {
             return;
         }
 
-        // Check for month roll
-        // 
-        if self._timeframe.increment_game_ticks() {
-            if company.queue_payroll() == false {
-                self._world_state = WorldState::Bust
+        let seen = self._messages.latest_seq();
+
+        // Close off whatever periods just ended - biggest first so the year's pay rise
+        // lands before the month's payroll, then the new week gets going
+        //
+        for rollover in self._timeframe.increment_game_ticks().iter().rev() {
+            match rollover {
+                Rollover::Year(year) => self.on_year_end(*year, company, software),
+                Rollover::Quarter(year, quarter) => self.on_quarter_end(*year, *quarter, company, software),
+                Rollover::Month(_) => self.on_month_end(company, software),
+                Rollover::Week => self.on_week(company, software)
             }
         }
 
@@ -102,6 +362,483 @@ impl World {
         // Update time
         //
         self._timeframe.set_current_time(time_now);
+
+        self.check_achievements(company, software);
+        self.check_objectives(company, software);
+
+        // Anything critical, or waiting on a decision, stops the clock
+        //
+        let important = self._messages.since(seen).iter().any(|message| message.severity == Severity::Critical || message.category == Category::Events);
+        if self._auto_pause && important {
+            self._timeframe.set_paused(true, time_now);
+        }
+    }
+
+    // Pay rises keep up with the economy, then everybody gets their annual review - unless
+    // we're skipping them this year
+    //
+    fn on_year_end(&mut self, year: i32, company: &mut Company, software: &Software) {
+        self.emit(Severity::Info, Category::Finance, format!("{} closed with {} customers and {} in the bank", year, software.customers(), company.cash_in_bank()));
+
+        let inflation = self._economy.salary_inflation();
+        company.apply_salary_inflation(inflation);
+        self.emit(Severity::Info, Category::People, format!("Annual pay rise of {}% for everyone", inflation));
+
+        if !company.reviews_enabled() {
+            company.skip_reviews();
+            self.emit(Severity::Warning, Category::People, format!("No reviews in {} - people feel forgotten", year));
+            return
+        }
+
+        let reviews = company.conduct_reviews(self.current_yearweek());
+        for (id, review) in reviews.iter() {
+            if let Some(level) = review.promoted {
+                self.emit(Severity::Good, Category::People, format!("{} promoted to {} - {} options granted", company.name_of(*id), level, review.equity));
+            }
+        }
+
+        let bonuses = reviews.iter().map(|(_, review)| review.bonus).sum::<u32>();
+        let below = reviews.iter().filter(|(_, review)| review.rating == Rating::Below).count();
+        self.emit(Severity::Info, Category::People, format!("{} reviews done - {} in bonuses with the next payroll, {} below expectations", reviews.len(), bonuses, below));
+    }
+
+    // Put the strongest senior person in charge of a department that has nobody running it
+    //
+    pub fn promote_from_within(&mut self, company: &mut Company) {
+        let (id, role) = match company.management_candidate() {
+            Some(candidate) => candidate,
+            None => {
+                self.emit(Severity::Warning, Category::People, "Nobody senior enough to run a department that needs it".to_string());
+                return
+            }
+        };
+
+        match company.promote_to_management(id) {
+            Ok(_) => self.emit(Severity::Good, Category::People, format!("{} promoted to {:?}", company.name_of(id), role)),
+            Err(error) => self.emit(Severity::Warning, Category::People, error)
+        }
+    }
+
+    fn on_quarter_end(&mut self, year: i32, quarter: u32, company: &Company, software: &Software) {
+        self.emit(Severity::Info, Category::Finance, format!("Q{} {} report - {} customers, {} a month recurring, {} in the bank",
+                                                             quarter, year, software.customers(), software.monthly_recurring_revenue(), company.cash_in_bank()));
+    }
+
+    // Customers are billed before we pay everyone
+    //
+    fn on_month_end(&mut self, company: &mut Company, software: &mut Software) {
+        let cash_before = company.cash_in_bank();
+        let revenue = software.monthly_recurring_revenue();
+        company.add_cash(revenue);
+        software.roll_customer_month();
+        company.roll_sales_month();
+
+        if company.queue_payroll() {
+            self._profitable_months = if company.cash_in_bank() > cash_before { self._profitable_months + 1 } else { 0 };
+            self.emit(Severity::Info, Category::Finance, format!("Billed {} and paid payroll - {} in the bank", revenue, company.cash_in_bank()));
+        } else {
+            self._world_state = WorldState::Bust;
+            self.emit(Severity::Critical, Category::Finance, "Payroll failed - we're bust".to_string());
+        }
+    }
+
+    // Code and dependencies get older every week and the market moves on. Contractors
+    // whose term is up leave, and the corners they cut turn into technical debt.
+    //
+    fn on_week(&mut self, company: &mut Company, software: &mut Software) {
+        self.update_economy();
+        for leaver in company.end_contracts(self.current_yearweek()) {
+            self.emit(Severity::Info, Category::People, format!("{}'s {} contract has ended", leaver.name(), leaver.contract().map(|contract| contract.supplier.to_string().to_lowercase()).unwrap_or_default()));
+        }
+        company.develop_skills(self.current_yearweek());
+        company.progress_onboarding();
+        company.recalculate_team_productivity();
+        software.age_code();
+        software.add_technical_debt(company.take_contract_debt());
+
+        // Customers weigh up what we've delivered this week - some of them leave
+        //
+        let churned = software.update_customers(company.get_support_responsiveness(software.support_load()), competitor::market_price(&self._competitors), &mut self._rng);
+        if churned > 0 {
+            self.emit(Severity::Warning, Category::Customers, format!("Lost {} customers to churn", churned));
+        }
+
+        // Salespeople work the pipeline and whatever closes becomes a customer
+        //
+        let conditions = SalesConditions { feature_richness: software.feature_richness(),
+                                           competition: self.competition_in_market(),
+                                           competitor_price: competitor::market_price(&self._competitors),
+                                           customer_budget: self._economy.customer_budget() };
+        for deal in company.update_sales_pipeline(&conditions, &mut self._rng) {
+            software.add_contract(deal.segment(), deal.seats(), deal.value());
+            self.emit(Severity::Good, Category::Sales, format!("Won a {} deal worth {} a month", deal.segment(), deal.value()));
+        }
+
+        self.update_competitors(company, software);
+        self.update_events(company, software);
+    }
+
+    fn check_achievements(&mut self, company: &Company, software: &Software) {
+        let context = AchievementContext { releases: software.releases(),
+                                           customers: software.customers(),
+                                           profitable_months: self._profitable_months,
+                                           employees: company.get_employees().len() as u16,
+                                           recessions_survived: self._recessions_survived,
+                                           ownership: company.ownership() };
+
+        let unlocked = self._achievements.check(&context, self._timeframe.get_current_yearweek());
+        for achievement in unlocked {
+            self.emit(Severity::Good, Category::Achievements, format!("Achievement unlocked - {} (+{})", achievement, achievement.points()));
+        }
+    }
+
+    // Has the game been won or lost?
+    //
+    fn check_objectives(&mut self, company: &Company, software: &Software) {
+        if self._world_state != WorldState::Nominal {
+            return;
+        }
+
+        let context = self.objective_context(company, software);
+
+        if self._objectives.won(&context) {
+            self._world_state = WorldState::Won;
+            self.emit(Severity::Good, Category::Finance, "Objectives complete - you've won".to_string());
+        } else if let Some(objective) = self._objectives.lost(&context) {
+            self._world_state = WorldState::Lost;
+            self.emit(Severity::Critical, Category::Finance, format!("Game lost - {}", objective));
+        } else if company.ownership() == Ownership::Acquired {
+            self._world_state = WorldState::Acquired;
+            self.emit(Severity::Critical, Category::Finance, "We've been acquired - the game is over".to_string());
+        }
+    }
+
+    // Ring the bell. The markets want a big enough, profitable company and won't
+    // touch anything in a recession.
+    //
+    pub fn attempt_ipo(&mut self, company: &mut Company, software: &Software) {
+        let valuation = self.valuation(company, software);
+
+        let refusal = if company.ownership() != Ownership::Private {
+            Some("We're not ours to float".to_string())
+        } else if valuation < IPO_MIN_VALUATION {
+            Some(format!("Bankers want a valuation of {} - we're at {}", IPO_MIN_VALUATION, valuation))
+        } else if self._profitable_months < IPO_PROFITABLE_MONTHS {
+            Some(format!("Bankers want {} profitable months in a row - we've had {}", IPO_PROFITABLE_MONTHS, self._profitable_months))
+        } else if self._economy.phase() == EconomicPhase::Recession {
+            Some("Nobody is buying new issues in a recession".to_string())
+        } else {
+            None
+        };
+
+        match refusal {
+            Some(reason) => self.emit(Severity::Warning, Category::Finance, reason),
+            None => {
+                company.raise_funding(valuation / 5);
+                company.set_ownership(Ownership::Public);
+                self.emit(Severity::Good, Category::Finance, format!("We're public - raised {} at a valuation of {}", valuation / 5, valuation));
+            }
+        }
+    }
+
+    pub fn set_name_pool(&mut self, names: NamePool) {
+        self._names = names
+    }
+
+    // Take somebody on from the job market where we're hiring, on local pay - they need
+    // onboarding before they pull their weight. Returns their ID if we found anyone.
+    //
+    pub fn hire(&mut self, company: &mut Company, employee_type: EmployeeType) -> Option<u16> {
+        let (age, compensation, efficiency, talent) = setup::typical_stats(employee_type);
+        let region = company.hiring_region();
+        let compensation = compensation * region.salary() / 100;
+
+        if self.hiring_pool(company) < MIN_HIRING_POOL {
+            self.emit(Severity::Warning, Category::People, format!("Nobody is looking for work as a {:?} in {} right now", employee_type, region));
+            return None;
+        }
+        let fee = compensation * RECRUITMENT_FEE_MONTHS / 12;
+        if company.cash_in_bank() < fee {
+            self.emit(Severity::Warning, Category::People, format!("Can't afford the recruiter's fee of {} to find a {:?}", fee, employee_type));
+            return None;
+        }
+        company.remove_cash(fee);
+
+        let taken: Vec<String> = company.get_employees().values().map(|employee| employee.name()).collect();
+        let name = self._names.generate(&mut self._rng, &taken);
+
+        let market = u16::min(10, self.hiring_pool(company) / 100);
+        let efficiency = efficiency * ( HIRE_MIN_EFFICIENCY + self._rng.gen_range(0, HIRE_EFFICIENCY_SPREAD) + market ) / 100;
+        let talent = ( talent + self._rng.gen_range(0, HIRE_TALENT_SPREAD) ).saturating_sub(HIRE_TALENT_SPREAD / 2);
+
+        // Most people come with something they're particularly good at - not every role has
+        // a skill to stand out in
+        //
+        let mut employee = Employee::new(employee_type, name.clone(), age, compensation, efficiency, talent);
+        let mut strength = String::new();
+        if let Some(specialism) = self._rng.choose(&Skills::specialisms(employee_type)) {
+            employee.specialise(*specialism);
+            strength = format!(", good at {}", specialism);
+        }
+        employee.set_region(region);
+
+        let id = company.hire_employee(employee);
+        self.emit(Severity::Good, Category::People, format!("Hired {} as a {:?} in {} for a fee of {} - efficiency {}, talent {}{}", name, employee_type, region, fee, efficiency, talent, strength));
+        Some(id)
+    }
+
+    // Start the move to a new architecture - the teams work it off alongside everything else
+    //
+    pub fn rearchitect(&mut self, software: &mut Software, architecture: Architecture) {
+        if software.rearchitect(architecture) {
+            self.emit(Severity::Info, Category::Product, format!("Started migrating to {} - {} dev days of work", architecture, software.migration_days()));
+        }
+    }
+
+    // Bring somebody in for a term through a supplier - experienced enough to start at
+    // full efficiency, but they don't come cheap. Returns their ID.
+    //
+    pub fn engage_contractor(&mut self, company: &mut Company, supplier: Supplier, employee_type: EmployeeType) -> u16 {
+        let (age, compensation, efficiency, talent) = setup::typical_stats(employee_type);
+        let taken: Vec<String> = company.get_employees().values().map(|employee| employee.name()).collect();
+        let name = self._names.generate(&mut self._rng, &taken);
+
+        let region = company.hiring_region();
+        let mut employee = Employee::new(employee_type, name.clone(), age, compensation * region.salary() / 100, efficiency, talent);
+        employee.set_region(region);
+
+        let contract = Contract::new(supplier, self.current_yearweek());
+        let id = company.engage_contractor(employee, contract);
+        let salary = company.employee(id).map(|employee| employee.salary()).unwrap_or(0);
+        self.emit(Severity::Good, Category::People, format!("Brought in {} as a {:?} through a {} contract - {} a month until {}", name, employee_type, supplier.to_string().to_lowercase(), salary, contract.ends));
+        id
+    }
+
+    pub fn extend_contract(&mut self, company: &mut Company, id: u16) {
+        match company.extend_contract(id) {
+            Ok(ends) => self.emit(Severity::Info, Category::People, format!("{} is staying on until {}", company.name_of(id), ends)),
+            Err(error) => self.emit(Severity::Warning, Category::People, error)
+        }
+    }
+
+    // Open an office somewhere we don't have one yet
+    //
+    pub fn open_office(&mut self, company: &mut Company) {
+        let region = match company.next_office() {
+            Some(region) => region,
+            None => {
+                self.emit(Severity::Info, Category::People, "We already have an office everywhere we could".to_string());
+                return
+            }
+        };
+
+        match company.open_office(region) {
+            Ok(()) => self.emit(Severity::Good, Category::People, format!("Opened an office in {} - local pay is {}% of headquarters", region, region.salary())),
+            Err(error) => self.emit(Severity::Warning, Category::People, error)
+        }
+    }
+
+    // Send somebody on a course
+    //
+    pub fn train(&mut self, company: &mut Company, id: u16) {
+        match company.send_on_training(id, self.current_yearweek()) {
+            Ok(gained) => self.emit(Severity::Info, Category::People, format!("{} went on a course - efficiency up {}", company.name_of(id), gained)),
+            Err(error) => self.emit(Severity::Warning, Category::People, error)
+        }
+    }
+
+    // Have somebody report to somebody else
+    //
+    pub fn set_manager(&mut self, company: &mut Company, id: u16, manager: u16) {
+        match company.set_manager(id, Some(manager)) {
+            Ok(()) => self.emit(Severity::Info, Category::People, format!("{} now reports to {}", company.name_of(id), company.name_of(manager))),
+            Err(error) => self.emit(Severity::Warning, Category::People, error)
+        }
+    }
+
+    // Put a new team together - it starts empty
+    //
+    pub fn form_team(&mut self, company: &mut Company, team_type: TeamType, mission: Mission) -> u16 {
+        let name = TEAM_NAMES.iter().find(|name| !company.teams().iter().any(|team| team.name() == **name))
+                             .map(|name| name.to_string())
+                             .unwrap_or(format!("Team {}", company.teams().len() + 1));
+
+        let id = company.create_team(name.clone(), team_type, mission, self.current_yearweek());
+        self.emit(Severity::Info, Category::People, format!("Formed {} - a {} team working on {}", name, team_type, mission));
+        id
+    }
+
+    pub fn disband_team(&mut self, company: &mut Company, id: u16) {
+        if let Some(team) = company.disband_team(id) {
+            self.emit(Severity::Info, Category::People, format!("Disbanded {} - {} people back in the pool", team.name(), team.get_size()));
+        }
+    }
+
+    pub fn assign_to_team(&mut self, company: &mut Company, employee: u16, id: u16) {
+        match company.assign_employee(employee, id, self.current_yearweek()) {
+            Ok(()) => {
+                let team = company.team(id).map(|team| team.name()).unwrap_or_default();
+                self.emit(Severity::Info, Category::People, format!("{} has joined {}", company.name_of(employee), team));
+            },
+            Err(error) => self.emit(Severity::Warning, Category::People, error)
+        }
+    }
+
+    // Go out to investors. Whether they bite and how much they put in depends on how
+    // much money is around and what we're already making.
+    //
+    pub fn raise_funding(&mut self, company: &mut Company, software: &Software) {
+        let yearweek = self._timeframe.get_current_yearweek();
+        if self._last_funding_round.is_some_and(|last| last.difference_weeks(&yearweek) < FUNDING_ROUND_WEEKS) {
+            self.emit(Severity::Info, Category::Finance, "Too soon since the last round - investors won't take the meeting".to_string());
+            return;
+        }
+        self._last_funding_round = Some(yearweek);
+
+        let availability = self._economy.funding_availability();
+        if self._rng.gen_range(0, 100) >= availability {
+            self.emit(Severity::Warning, Category::Finance, "Investors passed on this round".to_string());
+            return;
+        }
+
+        // Worked out in u64 as two years of a big consumer business' revenue won't fit
+        //
+        let funding = u64::max(1000, software.monthly_recurring_revenue() as u64 * 24) * availability as u64 / 50;
+        let funding = u64::min(funding, u32::MAX as u64) as u32;
+        company.raise_funding(funding);
+        self.emit(Severity::Good, Category::Finance, format!("Raised {} from investors", funding));
+    }
+
+    // The economy moves on and the job market follows it a step at a time
+    //
+    fn update_economy(&mut self) {
+        if let Some(phase) = self._economy.update_week(&mut self._rng) {
+            if phase == EconomicPhase::Recovery {
+                self._recessions_survived += 1;
+            }
+            let severity = match phase {
+                EconomicPhase::Recession | EconomicPhase::Peak => Severity::Warning,
+                EconomicPhase::Expansion | EconomicPhase::Recovery => Severity::Good
+            };
+            self.emit(severity, Category::Economy, format!("The economy has moved into {}", phase));
+        }
+
+        let target = self._economy.job_market_target();
+        if self._job_market < target {
+            self._job_market += 1;
+        } else if self._job_market > target {
+            self._job_market -= 1;
+        }
+    }
+
+    // Competitors make their moves against us, the broke ones leave the market and
+    // every so often somebody new turns up
+    //
+    fn update_competitors(&mut self, company: &mut Company, software: &mut Software) {
+        let rng = &mut self._rng;
+
+        let funding_availability = self._economy.funding_availability();
+        let mut news = Vec::new();
+
+        for competitor in self._competitors.iter_mut() {
+
+            // Running low on cash - whether they can raise depends on the economy
+            //
+            if competitor.runway_weeks() < 10 && rng.gen_range(0, 100) < funding_availability {
+                competitor.add_funding(rng.gen_range(100000, 1000001));
+            }
+
+            match competitor.update_week(rng) {
+                Some(CompetitorAction::PoachedEmployee) => {
+                    if let Some(employee) = company.poach_employee() {
+                        news.push((Severity::Warning, format!("{} poached {}", competitor.name(), employee.name())));
+                    }
+                },
+                Some(CompetitorAction::StoleCustomers) => {
+                    let lost = software.lose_to_competitor();
+                    if lost > 0 {
+                        news.push((Severity::Warning, format!("{} took {} of our customers", competitor.name(), lost)));
+                    }
+                },
+                Some(CompetitorAction::ReleasedFeature) => news.push((Severity::Info, format!("{} released a new feature", competitor.name()))),
+                Some(CompetitorAction::CutPrice) => news.push((Severity::Info, format!("{} cut their prices to {}%", competitor.name(), competitor.price()))),
+                Some(CompetitorAction::WentBust) => news.push((Severity::Good, format!("{} went bust", competitor.name()))),
+                None => ()
+            }
+        }
+
+        self._competitors.retain(|competitor| competitor.last_action() != Some(CompetitorAction::WentBust));
+
+        // Start-ups appear when money is easy to come by. Both are percentages, so the
+        // roll is out of 100 * 100 to keep the odds when funding is scarce.
+        //
+        if rng.gen_range(0, 100 * 100) < NEW_ENTRANT_CHANCE * funding_availability {
+            if let Some(entrant) = competitor::new_entrant(&self._competitors, rng) {
+                news.push((Severity::Warning, format!("{} has entered the market", entrant.name())));
+                self._competitors.push(entrant);
+            }
+        }
+
+        for (severity, text) in news {
+            self.emit(severity, Category::Market, text);
+        }
+    }
+
+    // Raise any events the world has triggered and take the default choice on anything
+    // that has waited too long for a decision
+    //
+    fn update_events(&mut self, company: &mut Company, software: &mut Software) {
+        let context = EventContext { employees: company.get_employees().len() as u16,
+                                     developers: company.get_number_of_employees(EmployeeType::Developer),
+                                     customers: software.customers(),
+                                     cash: company.cash_in_bank(),
+                                     security_risk: software.security_risk(),
+                                     valuation: self.valuation(company, software),
+                                     direction: company.direction(),
+                                     phase: self._economy.phase(),
+                                     yearweek: self._timeframe.get_current_yearweek() };
+
+        if self._events.check_triggers(&context, &mut self._rng) > 0 {
+            self.emit(Severity::Warning, Category::Events, "Something has come up that needs a decision".to_string());
+        }
+
+        for effects in self._events.expire(self._timeframe.get_current_yearweek()) {
+            World::apply_effects(effects, company, software, &mut self._rng);
+            self.emit(Severity::Info, Category::Events, "No decision made in time - the default was taken".to_string());
+        }
+    }
+
+    // The player's decision on the oldest pending event
+    //
+    pub fn resolve_event(&mut self, choice: usize, company: &mut Company, software: &mut Software) {
+        if let Some(effects) = self._events.resolve(choice, self._timeframe.get_current_yearweek()) {
+            World::apply_effects(effects, company, software, &mut self._rng);
+
+            let decision = self._events.log()[0].outcome;
+            self.emit(Severity::Info, Category::Events, format!("Decided: {}", decision));
+        }
+    }
+
+    fn apply_effects<R: Rng>(effects: &[Effect], company: &mut Company, software: &mut Software, rng: &mut R) {
+        for effect in effects {
+            match effect {
+                Effect::RemoveCash(percentage) => company.remove_cash(company.cash_in_bank() / 100 * *percentage as u32),
+                Effect::LoseKeyEmployee => { company.poach_employee(); },
+                Effect::LoseWeakestEmployee => { company.lay_off_weakest_employee(); },
+                Effect::RaiseKeyEmployee(percentage) => company.raise_key_employee(*percentage),
+                Effect::Reliability(change) => software.adjust_reliability(*change),
+                Effect::CustomerSatisfaction(change) => software.adjust_customer_satisfaction(*change),
+                Effect::AddAccount(segment, seats) => software.add_contract(*segment, *seats, *seats * segment.list_price_per_seat()),
+                Effect::AddCustomers(segment, accounts) => {
+                    for _account in 0..*accounts {
+                        software.add_account(*segment, rng);
+                    }
+                },
+                Effect::TechnicalDebt(technical_debt) => software.add_technical_debt(*technical_debt),
+                Effect::UpgradeDependencies => software.upgrade_all_dependencies(),
+                Effect::Acquired => company.set_ownership(Ownership::Acquired)
+            }
+        }
     }
 
     pub fn get_game_elapse_time(& self) -> chrono::Duration {
@@ -151,39 +888,21 @@ impl World {
 
         // Consequences
         //
-        // User Growth - only if we have released software can we have customers
+        // User Growth - only if we have released software can we have customers. Losing
+        // them is down to the weekly churn model.
         //
-        if software.releases() > 0 {
-
-            if software.usability_factor() > 0 {
-
-                let mut rng = rand::thread_rng();
-                let rand_number: f32 = rng.gen();
-                let rand_market =( rand_number * 100.0f32 ) as u16; // generates a number between 0 - 100
-
-                // What's the age of the software
-                //
-                if software.market_popularity(&self._timeframe.get_current_yearweek()) > rand_market {
-                    println!("Gaining customers / users");
-                    software.add_customers(1, false);
-                }
-            } else {
-
-                let mut rng = rand::thread_rng();
-                let rand_number: f32 = rng.gen();
-                let rand_market =( rand_number * 100.0f32 ) as u16; // generates a number between 0 - 100
-
-                // What's the age of the software
-                //
-                if software.market_popularity(&self._timeframe.get_current_yearweek()) < rand_market {
-                    println!("Losing customers / users");
-                    software.remove_customers(1);
-                }
+        if software.releases() > 0 && software.usability_factor() > 0 {
 
+            let rand_number: f32 = self._rng.gen();
+            let rand_market =( rand_number * 100.0f32 ) as u16; // generates a number between 0 - 100
 
+            // What's the age of the software
+            //
+            if software.market_popularity(&self._timeframe.get_current_yearweek()) as u32 * self._economy.customer_budget() as u32 / 100 > rand_market as u32 {
+                let customers = software.customers();
+                software.add_customers(company.direction() == CompanyDirection::B2B, company.consumer_signups(), &mut self._rng);
+                self.emit(Severity::Good, Category::Customers, format!("Gained {} customers / users", software.customers() - customers));
             }
-
-            
         }
 
 
@@ -194,10 +913,45 @@ impl World {
 
         // Software Growth
         //
-        let dev_capacity = company.get_development_capacity(software.reliability(), software.quality());
-        
-        if dev_capacity > 50 {
-            software.work_on_features(company.get_number_of_employees(EmployeeType::Developer), dev_capacity, 3);
+        // Product teams build features and platform teams keep dependencies up to date.
+        // Outdated dependencies drag on everything we do and whatever the company has set
+        // aside for upgrade work comes off the top of product time.
+        //
+        let product = company.get_development_capacity(Mission::Product, software.reliability(), software.quality(), software.architecture());
+        let platform = company.get_development_capacity(Mission::Platform, software.reliability(), software.quality(), software.architecture());
+
+        let dev_capacity = software.apply_upgrade_drag(product.productivity);
+        let upgrade_capacity = ( dev_capacity as u32 * company.upgrade_focus() as u32 / 100 ) as u16;
+        let mut feature_capacity = dev_capacity - upgrade_capacity;
+
+        software.work_on_upgrades(product.effective_developers, upgrade_capacity, 3);
+
+        // A migration to a new architecture takes the platform team and half of what the
+        // product team had for features until it's done
+        //
+        if let Some(architecture) = software.migrating_to() {
+            let migration_capacity = feature_capacity / 2;
+            feature_capacity -= migration_capacity;
+
+            let migrated = software.work_on_migration(product.effective_developers, migration_capacity, 3) ||
+                           software.work_on_migration(platform.effective_developers, software.apply_upgrade_drag(platform.productivity), 3);
+            if migrated {
+                self.emit(Severity::Good, Category::Product, format!("Migration to {} complete", architecture));
+            }
+        } else {
+            software.work_on_upgrades(platform.effective_developers, software.apply_upgrade_drag(platform.productivity), 3);
+        }
+
+        if feature_capacity > 50 {
+            software.work_on_features(product.effective_developers, feature_capacity, 3);
+        }
+
+
+        // Ready for Release? - whatever has been built since the last one goes out
+        //
+        if software.ready_for_release() {
+            software.release(self._timeframe.get_current_yearweek());
+            self.emit(Severity::Good, Category::Product, format!("Release {} shipped", software.releases()));
         }
 
 
@@ -260,11 +1014,311 @@ impl World {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::employee::SkillSource;
+    use crate::employee::contract::CONTRACT_TERM_WEEKS;
+    use crate::company::workplace::{Policy, Region};
+    use crate::software::customers::Segment;
 
     #[test]
     fn time_tests() {
 
-        let world = World::new(100, 100, 100, 100, 0);
+        let world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
         assert_eq!(world.game_ticks(), 0);
     }
+
+    #[test]
+    fn bust_message_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(0, CompanyDirection::B2C);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        company.add_employee(crate::employee::Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 1200, 100, 85));
+
+        // The world is seeded so the competitors do the same thing every run - none of them
+        // poaches our only developer before payroll fails
+        for _tick in 0..20 {
+            world.increment_game_ticks(&mut company, &mut software, Local::now());
+        }
+
+        let critical = world.messages().filtered(Severity::Critical);
+        assert_eq!(critical.len(), 1);
+        assert_eq!(critical[0].category, Category::Finance);
+        assert!(world.is_paused());
+    }
+
+    #[test]
+    fn advance_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2C);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+
+        world.advance_to_next_week(&mut company, &mut software);
+        assert_eq!(world.game_year_week(), "2000-02");
+        assert!(world.is_paused());
+
+        world.advance_to_next_month(&mut company, &mut software);
+        assert_eq!(world.game_month(), 2);
+        assert_eq!(world.game_year_week(), "2000-06");
+    }
+
+    #[test]
+    fn release_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2C);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        world._auto_pause = false;
+
+        // Nothing shipped means nobody signs up
+        world.advance_to_next_week(&mut company, &mut software);
+        assert_eq!(software.customers(), 0);
+
+        // Consumers start arriving once the first release is out
+        software.work_on_features(2, 100, 20);
+        for _week in 0..10 {
+            world.advance_to_next_week(&mut company, &mut software);
+        }
+        assert_eq!(software.releases(), 1);
+        assert!(software.customer_base().segment_customers(Segment::Consumer) > 0);
+        assert!(world.messages().messages().iter().any(|message| message.category == Category::Product));
+    }
+
+    #[test]
+    fn rearchitect_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2B);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 30, 70, 100, 85));
+        world._auto_pause = false;
+
+        // The move takes weeks of developer time and we stay where we are until it's done
+        world.rearchitect(&mut software, Architecture::Monolith);
+        world.advance_to_next_week(&mut company, &mut software);
+        assert_eq!(software.architecture(), Architecture::ProofofConcept);
+        assert_eq!(software.migrating_to(), Some(Architecture::Monolith));
+
+        for _week in 0..10 {
+            world.advance_to_next_week(&mut company, &mut software);
+        }
+        assert_eq!(software.architecture(), Architecture::Monolith);
+        assert_eq!(software.migrating_to(), None);
+        assert!(software.technical_debt() > 0);
+    }
+
+    #[test]
+    fn hire_onboarding_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2B);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        world._auto_pause = false;
+
+        // Somebody hired in the game gets up to speed week by week
+        let hire = world.hire(&mut company, EmployeeType::Developer).unwrap();
+        assert_eq!(company.employee(hire).unwrap().onboarding(), crate::employee::ONBOARDING_WEEKS);
+
+        world.advance_to_next_week(&mut company, &mut software);
+        assert_eq!(company.employee(hire).unwrap().onboarding(), crate::employee::ONBOARDING_WEEKS - 1);
+    }
+
+    #[test]
+    fn first_release_achievement_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2B);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 30, 70, 100, 85));
+        world._auto_pause = false;
+
+        // Developers write enough for a release within a few weeks and shipping it is worth something
+        for _week in 0..8 {
+            world.advance_to_next_week(&mut company, &mut software);
+        }
+        assert!(software.releases() > 0);
+        assert!(world.achievements().has(achievements::Achievement::FirstRelease));
+    }
+
+    #[test]
+    fn event_effects_test() {
+
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 23, 35, 60, 95));
+
+        // Costs scale with what's in the bank, every new customer is an account of their own and
+        // cost cutting takes the weakest rather than the most talented
+        World::apply_effects(&[Effect::RemoveCash(10), Effect::AddCustomers(Segment::SMB, 3), Effect::LoseWeakestEmployee],
+                             &mut company, &mut software, &mut rand::thread_rng());
+        assert_eq!(company.cash_in_bank(), 90);
+        assert_eq!(software.customers(), 3);
+        assert_eq!(company.get_employees().values().map(|employee| employee.name()).collect::<Vec<String>>(), vec!["Developer 1".to_string()]);
+    }
+
+    #[test]
+    fn objectives_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2C);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        world.set_objectives(Objectives::from_conditions(&[objectives::Condition::Cash { min: 200000 }],
+                                                         &[objectives::Condition::FundingRaised { min: 1 }]));
+
+        world.increment_game_ticks(&mut company, &mut software, Local::now());
+        assert_eq!(world.world_state(), WorldState::Nominal);
+
+        company.raise_funding(1000);
+        world.increment_game_ticks(&mut company, &mut software, Local::now());
+        assert_eq!(world.world_state(), WorldState::Lost);
+        assert!(world.is_paused());
+    }
+
+    #[test]
+    fn ipo_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(IPO_MIN_VALUATION, CompanyDirection::B2B);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        world.set_objectives(Objectives::from_conditions(&[objectives::Condition::Ipo], &[]));
+        assert_eq!(world.valuation(&company, &software), IPO_MIN_VALUATION);
+
+        // Not until we've shown we can make money
+        world.attempt_ipo(&mut company, &software);
+        assert_eq!(company.ownership(), Ownership::Private);
+
+        world._profitable_months = IPO_PROFITABLE_MONTHS;
+        world.attempt_ipo(&mut company, &software);
+        assert_eq!(company.ownership(), Ownership::Public);
+        assert_eq!(company.funding_raised(), IPO_MIN_VALUATION / 5);
+
+        world.increment_game_ticks(&mut company, &mut software, Local::now());
+        assert_eq!(world.world_state(), WorldState::Won);
+        assert_eq!(world.scorecard(&company, &software).score, 100);
+
+        // Going public is an achievement and winning in the first week gets the full time bonus
+        assert!(world.achievements().has(achievements::Achievement::WentPublic));
+        assert_eq!(world.score(&company, &software).time_bonus, 100);
+    }
+
+    #[test]
+    fn hire_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100, CompanyDirection::B2B);
+        company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+
+        // New people get a name from the pool nobody here has already, and the recruiter takes a cut
+        world.set_name_pool(NamePool { first: vec!["Ada".to_string()], last: vec!["Lovelace".to_string(), "Byron".to_string()] });
+        let first = world.hire(&mut company, EmployeeType::Developer).unwrap();
+        let second = world.hire(&mut company, EmployeeType::Developer).unwrap();
+        let hired = company.employee(first).unwrap();
+        assert_eq!(first, 2);
+        assert!(hired.is_onboarding());
+        assert!(hired.efficiency() < 100);
+        assert!(hired.name().starts_with("Ada "));
+        assert_ne!(hired.name(), company.employee(second).unwrap().name());
+        assert_eq!(company.cash_in_bank(), 100 - 2 * 17);
+
+        world.train(&mut company, first);
+        assert_eq!(company.cash_in_bank(), 100 - 2 * 17 - crate::company::TRAINING_COST);
+        assert_eq!(company.employee(first).unwrap().skill_history().len(), 1);
+
+        // Remote working widens the pool and pay follows where people live
+        assert_eq!(world.hiring_pool(&company), 100);
+        company.set_policy(Policy::Remote);
+        assert_eq!(world.hiring_pool(&company), 160);
+        company.cycle_hiring_region();
+        company.add_cash(100);
+        let remote = world.hire(&mut company, EmployeeType::Developer).unwrap();
+        assert_eq!(company.employee(remote).unwrap().region(), Region::Berlin);
+        assert_eq!(company.employee(remote).unwrap().compensation(), 63);
+
+        // No recruiter without the fee, and nobody to find when nobody is looking
+        let employees = company.get_employees().len();
+        company.remove_cash(company.cash_in_bank());
+        assert_eq!(world.hire(&mut company, EmployeeType::Developer), None);
+        company.add_cash(100);
+        world._job_market = 20;
+        assert_eq!(world.hire(&mut company, EmployeeType::Developer), None);
+        assert_eq!(company.get_employees().len(), employees);
+        assert_eq!(company.cash_in_bank(), 100);
+
+        // An accountant has no skill to specialise in but can still be hired
+        world._job_market = 100;
+        let accountant = world.hire(&mut company, EmployeeType::Accountant).unwrap();
+        assert_eq!(company.employee(accountant).unwrap().skills(), &Skills::typical(EmployeeType::Accountant));
+
+        // Anybody but the executives
+        company.cycle_hiring_role();
+        assert_eq!(company.hiring_role(), EmployeeType::Tester);
+        for _role in 0..7 {
+            company.cycle_hiring_role();
+        }
+        assert_eq!(company.hiring_role(), EmployeeType::Developer);
+    }
+
+    #[test]
+    fn contractor_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2B);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        world._auto_pause = false;
+
+        let id = world.engage_contractor(&mut company, Supplier::Freelance, EmployeeType::Developer);
+        assert_eq!(company.employee(id).unwrap().efficiency(), setup::typical_stats(EmployeeType::Developer).2);
+
+        // Gone at the end of the term unless somebody keeps them on
+        for _week in 0..CONTRACT_TERM_WEEKS {
+            world.advance_to_next_week(&mut company, &mut software);
+        }
+        assert!(company.employee(id).is_none());
+        assert!(world.messages().messages().iter().any(|message| message.text.ends_with("freelance contract has ended")));
+        assert!(software.technical_debt() > 0);
+    }
+
+    #[test]
+    fn year_end_test() {
+
+        let mut world = World::new(100, 100, 100, Timeframe::new(100, 0, timeframe::START_YEARWEEK, timeframe::DEFAULT_TICKS_PER_WEEK), 0);
+        let mut company = Company::new(100000, CompanyDirection::B2C);
+        let mut software = Software::new(0, 0, 0, 0, timeframe::START_YEARWEEK);
+        world._auto_pause = false;
+
+        // 2000 ends after week 52 - the reports go out before the first payroll of 2001
+        for _tick in 0..52 * world.ticks_per_week() {
+            world.increment_game_ticks(&mut company, &mut software, Local::now());
+        }
+        assert_eq!(world.game_year_week(), "2001-01");
+
+        let reports: Vec<String> = world.messages().messages().iter().filter(|message| message.yearweek == world._timeframe.get_current_yearweek())
+                                                                 .map(|message| message.text.clone()).collect();
+        assert!(reports[0].starts_with("2000 closed"));
+        assert!(reports[2].starts_with("0 reviews done"));
+        assert!(reports[3].starts_with("Q4 2000 report"));
+        assert!(reports[4].starts_with("Billed"));
+
+        // Skipping reviews means no bonuses to pay but costs goodwill
+        let developer = company.add_employee(Employee::new(EmployeeType::Developer, "Developer 1".to_string(), 30, 70, 100, 85));
+        company.toggle_reviews();
+        world.on_year_end(2001, &mut company, &software);
+        assert_eq!(company.employee(developer).unwrap().skipped_reviews(), 1);
+        assert!(company.employee(developer).unwrap().morale() < 70);
+        assert_eq!(company.pending_bonuses(), 0);
+
+        // Options handed out at a strong review come out of what the company is worth to everybody else
+        let valuation = world.valuation(&company, &software);
+        assert_eq!(world.diluted_valuation(&company, &software), valuation);
+        let mut star = Employee::new(EmployeeType::Developer, "Developer 2".to_string(), 30, 70, 104, 100);
+        star.learn(100, SkillSource::Training, world.current_yearweek());
+        assert_eq!(star.review(world.current_yearweek()).equity, 10);
+        company.add_employee(star);
+        assert_eq!(company.options_granted(), 10);
+        assert_eq!(world.diluted_valuation(&company, &software), valuation * 99 / 100);
+    }
 }
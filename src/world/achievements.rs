@@ -0,0 +1,132 @@
+use std::fmt;
+
+use crate::company::Ownership;
+use super::timeframe::YearWeek;
+
+// Milestones worth celebrating along the way
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Achievement {
+    FirstRelease,
+    FirstCustomer,
+    FirstProfitableMonth,
+    HundredEmployees,
+    SurvivedRecession,
+    WentPublic,
+    Acquired
+}
+
+impl Achievement {
+
+    pub fn all() -> [Achievement; 7] {
+        [Achievement::FirstRelease, Achievement::FirstCustomer, Achievement::FirstProfitableMonth, Achievement::HundredEmployees,
+         Achievement::SurvivedRecession, Achievement::WentPublic, Achievement::Acquired]
+    }
+
+    // What it's worth towards the score
+    //
+    pub fn points(&self) -> u32 {
+        match self {
+            Achievement::FirstRelease | Achievement::FirstCustomer => 500,
+            Achievement::FirstProfitableMonth => 1000,
+            Achievement::HundredEmployees | Achievement::SurvivedRecession => 2500,
+            Achievement::WentPublic | Achievement::Acquired => 5000
+        }
+    }
+
+    fn earned(&self, context: &AchievementContext) -> bool {
+        match self {
+            Achievement::FirstRelease => context.releases > 0,
+            Achievement::FirstCustomer => context.customers > 0,
+            Achievement::FirstProfitableMonth => context.profitable_months > 0,
+            Achievement::HundredEmployees => context.employees >= 100,
+            Achievement::SurvivedRecession => context.recessions_survived > 0,
+            Achievement::WentPublic => context.ownership == Ownership::Public,
+            Achievement::Acquired => context.ownership == Ownership::Acquired
+        }
+    }
+}
+
+impl fmt::Display for Achievement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Achievement::FirstRelease => write!(f, "Shipped it"),
+            Achievement::FirstCustomer => write!(f, "Somebody pays us"),
+            Achievement::FirstProfitableMonth => write!(f, "In the black"),
+            Achievement::HundredEmployees => write!(f, "A hundred strong"),
+            Achievement::SurvivedRecession => write!(f, "Weathered the storm"),
+            Achievement::WentPublic => write!(f, "Ringing the bell"),
+            Achievement::Acquired => write!(f, "Exit")
+        }
+    }
+}
+
+// Snapshot of the game for checking achievements
+//
+pub struct AchievementContext {
+    pub releases: u16,
+    pub customers: u32,
+    pub profitable_months: u16,
+    pub employees: u16,
+    pub recessions_survived: u16,
+    pub ownership: Ownership
+}
+
+pub struct Achievements {
+    _unlocked: Vec<(Achievement, YearWeek)>     // in the order they happened
+}
+
+impl Achievements {
+
+    pub fn new() -> Achievements {
+        Achievements { _unlocked: Vec::new() }
+    }
+
+    pub fn unlocked(&self) -> &Vec<(Achievement, YearWeek)> {
+        &self._unlocked
+    }
+
+    pub fn has(&self, achievement: Achievement) -> bool {
+        self._unlocked.iter().any(|(unlocked, _)| *unlocked == achievement)
+    }
+
+    pub fn points(&self) -> u32 {
+        self._unlocked.iter().map(|(achievement, _)| achievement.points()).sum()
+    }
+
+    // Unlock anything newly earned - returns what was unlocked
+    //
+    pub fn check(&mut self, context: &AchievementContext, yearweek: YearWeek) -> Vec<Achievement> {
+        let earned: Vec<Achievement> = Achievement::all().iter()
+                                                         .filter(|achievement| !self.has(**achievement) && achievement.earned(context))
+                                                         .copied()
+                                                         .collect();
+
+        self._unlocked.extend(earned.iter().map(|achievement| (*achievement, yearweek)));
+        earned
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn achievements_test() {
+        let mut achievements = Achievements::new();
+        let mut context = AchievementContext { releases: 1, customers: 0, profitable_months: 0, employees: 4,
+                                               recessions_survived: 0, ownership: Ownership::Private };
+        let yearweek = YearWeek::new(2000, 10).unwrap();
+
+        assert_eq!(achievements.check(&context, yearweek), vec![Achievement::FirstRelease]);
+        assert!(achievements.check(&context, yearweek).is_empty());
+
+        context.customers = 10;
+        context.recessions_survived = 1;
+        assert_eq!(achievements.check(&context, yearweek), vec![Achievement::FirstCustomer, Achievement::SurvivedRecession]);
+        assert_eq!(achievements.points(), 3500);
+    }
+
+}
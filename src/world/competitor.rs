@@ -0,0 +1,215 @@
+use rand::Rng;
+use std::fmt;
+
+// Who else is out there
+//
+const COMPETITOR_NAMES: [&str; 8] = ["Initech", "Globex", "Vandelay Software", "Massive Dynamic",
+                                     "Soylent Systems", "Cyberdyne", "Gringotts Cloud", "Tyrell Apps"];
+
+// What a competitor did this week
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CompetitorAction {
+    ReleasedFeature,
+    CutPrice,
+    PoachedEmployee,
+    StoleCustomers,
+    WentBust
+}
+
+impl fmt::Display for CompetitorAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+pub struct Competitor {
+    _name: String,
+    _product_quality: u16,          // 0 - 100
+    _feature_richness: u16,         // 0 - 100
+    _price: u16,                    // percentage of our list price
+    _funding: u32,                  // cash left
+    _burn_rate: u32,                // cash spent a week
+    _aggressiveness: u16,           // 0 - 100
+    _last_action: Option<CompetitorAction>
+}
+
+impl Competitor {
+
+    pub fn new(name: String, product_quality: u16, feature_richness: u16, price: u16, funding: u32, burn_rate: u32, aggressiveness: u16) -> Competitor {
+        Competitor { _name: name,
+                     _product_quality: product_quality,
+                     _feature_richness: feature_richness,
+                     _price: price,
+                     _funding: funding,
+                     _burn_rate: burn_rate,
+                     _aggressiveness: aggressiveness,
+                     _last_action: None }
+    }
+
+    // A new entrant with a random product and war chest
+    //
+    pub fn generate<R: Rng>(name: String, rng: &mut R) -> Competitor {
+        let funding = rng.gen_range(50000, 500001);
+
+        Competitor::new(name,
+                        rng.gen_range(30, 71),
+                        rng.gen_range(20, 61),
+                        rng.gen_range(80, 121),
+                        funding,
+                        funding / rng.gen_range(100, 261),
+                        rng.gen_range(20, 81))
+    }
+
+    pub fn name(&self) -> String {
+        self._name.to_string()
+    }
+
+    pub fn product_quality(&self) -> u16 {
+        self._product_quality
+    }
+
+    pub fn feature_richness(&self) -> u16 {
+        self._feature_richness
+    }
+
+    pub fn price(&self) -> u16 {
+        self._price
+    }
+
+    pub fn funding(&self) -> u32 {
+        self._funding
+    }
+
+    pub fn aggressiveness(&self) -> u16 {
+        self._aggressiveness
+    }
+
+    pub fn last_action(&self) -> Option<CompetitorAction> {
+        self._last_action
+    }
+
+    pub fn runway_weeks(&self) -> u32 {
+        self._funding.checked_div(self._burn_rate).unwrap_or(u32::MAX)
+    }
+
+    pub fn add_funding(&mut self, funding: u32) {
+        self._funding += funding;
+    }
+
+    // How much of a threat this competitor is (0 - 200) - a good product sold cheaply
+    // by an aggressive team is the worst kind
+    //
+    pub fn strength(&self) -> u16 {
+        let product = ( self._product_quality + self._feature_richness ) as u32 / 2;
+        let price_factor = 200 - u16::clamp(self._price, 50, 150) as u32;
+
+        ( product * ( 100 + self._aggressiveness as u32 ) / 200 * price_factor / 100 ) as u16
+    }
+
+    // Weekly move - spend money and, the more aggressive they are, do something about us
+    //
+    pub fn update_week<R: Rng>(&mut self, rng: &mut R) -> Option<CompetitorAction> {
+        self._funding = self._funding.saturating_sub(self._burn_rate);
+
+        self._last_action = if self._funding == 0 {
+            Some(CompetitorAction::WentBust)
+        } else if rng.gen_range(0, 100) >= self._aggressiveness / 2 {
+            None
+        } else {
+            match rng.gen_range(0, 4) {
+                0 => {
+                    self._feature_richness = u16::min(100, self._feature_richness + 5);
+                    self._product_quality = self._product_quality.saturating_sub(1);
+                    Some(CompetitorAction::ReleasedFeature)
+                },
+                1 => {
+                    self._price = u16::max(50, self._price - 5);
+                    Some(CompetitorAction::CutPrice)
+                },
+                2 => Some(CompetitorAction::PoachedEmployee),
+                _ => Some(CompetitorAction::StoleCustomers)
+            }
+        };
+
+        return self._last_action
+    }
+}
+
+
+// Starting line-up of competitors - roughly one for every 100 points of competition
+//
+pub fn generate_competitors<R: Rng>(competition_in_market: u16, rng: &mut R) -> Vec<Competitor> {
+    let count = usize::clamp(competition_in_market as usize / 100, 1, COMPETITOR_NAMES.len());
+
+    COMPETITOR_NAMES.iter().take(count).map(|name| Competitor::generate(name.to_string(), rng)).collect()
+}
+
+// Somebody new to join the market, if there are any names left
+//
+pub fn new_entrant<R: Rng>(competitors: &[Competitor], rng: &mut R) -> Option<Competitor> {
+    COMPETITOR_NAMES.iter()
+                    .find(|name| !competitors.iter().any(|competitor| competitor.name() == **name))
+                    .map(|name| Competitor::generate(name.to_string(), rng))
+}
+
+// Overall pressure in the market (0 - 1000)
+//
+pub fn competition_in_market(competitors: &[Competitor]) -> u16 {
+    let competition: u32 = competitors.iter().map(|competitor| competitor.strength() as u32 * 2).sum();
+    u32::min(1000, competition) as u16
+}
+
+// Average competitor price as a percentage of our list price
+//
+pub fn market_price(competitors: &[Competitor]) -> u16 {
+    if competitors.is_empty() {
+        return 100
+    }
+
+    ( competitors.iter().map(|competitor| competitor.price() as u32).sum::<u32>() / competitors.len() as u32 ) as u16
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn competitor_strength_test() {
+        let mut competitor = Competitor::new("Initech".to_string(), 60, 40, 100, 1000, 10, 50);
+        assert_eq!(competitor.strength(), 37);
+
+        competitor._price = 50;
+        assert_eq!(competitor.strength(), 55);
+        assert_eq!(competition_in_market(&[competitor]), 110);
+    }
+
+    #[test]
+    fn competitor_update_test() {
+        let mut competitor = Competitor::new("Initech".to_string(), 60, 40, 100, 25, 10, 50);
+
+        // A zero rng always acts and always picks the first move
+        let mut rng = StepRng::new(0, 0);
+        assert_eq!(competitor.update_week(&mut rng), Some(CompetitorAction::ReleasedFeature));
+        assert_eq!(competitor.feature_richness(), 45);
+        assert_eq!(competitor.funding(), 15);
+
+        competitor.update_week(&mut rng);
+        assert_eq!(competitor.update_week(&mut rng), Some(CompetitorAction::WentBust));
+    }
+
+    #[test]
+    fn competitor_generation_test() {
+        let mut rng = StepRng::new(0, 0);
+        let competitors = generate_competitors(300, &mut rng);
+
+        assert_eq!(competitors.len(), 3);
+        assert_eq!(market_price(&competitors), 80);
+        assert_eq!(new_entrant(&competitors, &mut rng).unwrap().name(), "Massive Dynamic");
+    }
+
+}
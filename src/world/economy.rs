@@ -0,0 +1,192 @@
+use rand::Rng;
+use std::fmt;
+
+// Weeks of history we keep for the trend chart
+//
+const HISTORY_WEEKS: usize = 104;
+
+// Where we are in the boom/bust cycle
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum EconomicPhase {
+    Expansion,
+    Peak,
+    Recession,
+    Recovery
+}
+
+impl EconomicPhase {
+
+    fn next(&self) -> EconomicPhase {
+        match self {
+            EconomicPhase::Expansion => EconomicPhase::Peak,
+            EconomicPhase::Peak => EconomicPhase::Recession,
+            EconomicPhase::Recession => EconomicPhase::Recovery,
+            EconomicPhase::Recovery => EconomicPhase::Expansion
+        }
+    }
+
+    // How long each phase lasts in weeks (min, max)
+    //
+    fn duration(&self) -> (u16, u16) {
+        match self {
+            EconomicPhase::Expansion => (100, 300),
+            EconomicPhase::Peak => (8, 20),
+            EconomicPhase::Recession => (20, 80),
+            EconomicPhase::Recovery => (20, 60)
+        }
+    }
+
+    // Weekly movement of the economic index (min, max)
+    //
+    fn drift(&self) -> (i16, i16) {
+        match self {
+            EconomicPhase::Expansion => (0, 4),
+            EconomicPhase::Peak => (-1, 2),
+            EconomicPhase::Recession => (-6, -1),
+            EconomicPhase::Recovery => (1, 4)
+        }
+    }
+}
+
+impl fmt::Display for EconomicPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+pub struct Economy {
+    _index: u16,                    // 0 - 1000 - the global economic factors
+    _phase: EconomicPhase,
+    _weeks_in_phase: u16,
+    _phase_length: u16,             // weeks until the phase turns
+    _interest_rate: u16,            // basis points
+    _history: Vec<u16>              // weekly index, oldest first
+}
+
+impl Economy {
+
+    pub fn new(index: u16) -> Economy {
+        Economy { _index: u16::min(1000, index),
+                  _phase: EconomicPhase::Expansion,
+                  _weeks_in_phase: 0,
+                  _phase_length: EconomicPhase::Expansion.duration().0,
+                  _interest_rate: 500,
+                  _history: vec![u16::min(1000, index)] }
+    }
+
+    pub fn index(&self) -> u16 {
+        self._index
+    }
+
+    pub fn phase(&self) -> EconomicPhase {
+        self._phase
+    }
+
+    pub fn interest_rate(&self) -> u16 {
+        self._interest_rate
+    }
+
+    pub fn history(&self) -> &Vec<u16> {
+        &self._history
+    }
+
+    // Weekly move of the economy. Returns the new phase when the cycle turns.
+    //
+    pub fn update_week<R: Rng>(&mut self, rng: &mut R) -> Option<EconomicPhase> {
+        let (min_drift, max_drift) = self._phase.drift();
+        let drift = rng.gen_range(min_drift, max_drift + 1);
+        self._index = i16::clamp(self._index as i16 + drift, 0, 1000) as u16;
+
+        // The central bank leans against the cycle a quarter point at a time
+        //
+        if rng.gen_range(0, 100) < 5 {
+            self._interest_rate = match self._phase {
+                EconomicPhase::Expansion | EconomicPhase::Peak => u16::min(2000, self._interest_rate + 25),
+                EconomicPhase::Recession | EconomicPhase::Recovery => self._interest_rate.saturating_sub(25)
+            };
+        }
+
+        self._history.push(self._index);
+        if self._history.len() > HISTORY_WEEKS {
+            self._history.remove(0);
+        }
+
+        self._weeks_in_phase += 1;
+        if self._weeks_in_phase < self._phase_length {
+            return None
+        }
+
+        self._phase = self._phase.next();
+        self._weeks_in_phase = 0;
+
+        let (min_weeks, max_weeks) = self._phase.duration();
+        self._phase_length = rng.gen_range(min_weeks, max_weeks + 1);
+
+        return Some(self._phase)
+    }
+
+    // What customers have to spend as a percentage of normal (50 - 150)
+    //
+    pub fn customer_budget(&self) -> u16 {
+        50 + self._index / 10
+    }
+
+    // How easy it is to raise money (0 - 100) - cheap money in a good economy
+    //
+    pub fn funding_availability(&self) -> u16 {
+        i32::clamp(self._index as i32 / 10 - self._interest_rate as i32 / 50, 0, 100) as u16
+    }
+
+    // Annual salary inflation percentage - good times mean competition for people
+    //
+    pub fn salary_inflation(&self) -> u16 {
+        1 + self._index / 250
+    }
+
+    // Where the job market is heading (0 - 1000) - a recession puts people on the market
+    //
+    pub fn job_market_target(&self) -> u16 {
+        1000 - self._index
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn economy_derived_test() {
+        let economy = Economy::new(500);
+
+        assert_eq!(economy.customer_budget(), 100);
+        assert_eq!(economy.funding_availability(), 40);
+        assert_eq!(economy.salary_inflation(), 3);
+        assert_eq!(economy.job_market_target(), 500);
+    }
+
+    #[test]
+    fn economy_cycle_test() {
+        let mut economy = Economy::new(500);
+
+        // A zero rng always takes the lowest drift and shortest phase
+        let mut rng = StepRng::new(0, 0);
+        for _week in 0..99 {
+            assert_eq!(economy.update_week(&mut rng), None);
+        }
+        assert_eq!(economy.update_week(&mut rng), Some(EconomicPhase::Peak));
+        assert_eq!(economy.index(), 500);
+        assert_eq!(economy.history().len(), 101);
+
+        for _week in 0..8 {
+            economy.update_week(&mut rng);
+        }
+        assert_eq!(economy.phase(), EconomicPhase::Recession);
+        assert_eq!(economy.index(), 492);
+    }
+
+}
@@ -0,0 +1,361 @@
+use rand::Rng;
+
+use crate::company::CompanyDirection;
+use crate::software::customers::Segment;
+use super::economy::EconomicPhase;
+use super::timeframe::YearWeek;
+
+// Weeks an event waits for a decision before the default choice is taken
+//
+pub const DECISION_WEEKS: u32 = 4;
+
+// Entries kept in the event log
+//
+const LOG_LENGTH: usize = 50;
+
+// Conditions that must all hold for an event to fire
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Trigger {
+    MinEmployees(u16),
+    MinDevelopers(u16),
+    MinCustomers(u32),
+    MinCash(u32),
+    MinSecurityRisk(u16),
+    MinValuation(u32),
+    Direction(CompanyDirection),
+    Phase(EconomicPhase),
+    From(i32, u32)                  // year, week
+}
+
+// What happens when a choice is made
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Effect {
+    RemoveCash(u16),                // percentage of the cash in the bank
+    LoseKeyEmployee,
+    LoseWeakestEmployee,
+    RaiseKeyEmployee(u16),          // percentage pay rise
+    Reliability(i16),
+    CustomerSatisfaction(i16),
+    AddAccount(Segment, u32),       // one account with this many seats
+    AddCustomers(Segment, u32),     // accounts of a typical size
+    TechnicalDebt(u16),
+    UpgradeDependencies,
+    Acquired                        // we sell the company
+}
+
+pub struct Choice {
+    pub label: &'static str,
+    pub effects: &'static [Effect]
+}
+
+pub struct EventDefinition {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub triggers: &'static [Trigger],
+    pub weekly_chance: u16,         // chance in a thousand once triggered
+    pub once: bool,                 // only ever happens once
+    pub choices: &'static [Choice]  // the last choice is taken if the player doesn't decide
+}
+
+// Everything that can happen to us
+//
+pub const EVENTS: [EventDefinition; 7] = [
+    EventDefinition {
+        id: "competing_offer",
+        title: "A key developer has a competing offer",
+        triggers: &[Trigger::MinDevelopers(2)],
+        weekly_chance: 10,
+        once: false,
+        choices: &[Choice { label: "Match the offer (+20% pay)", effects: &[Effect::RaiseKeyEmployee(20)] },
+                   Choice { label: "Let them go", effects: &[Effect::LoseKeyEmployee] }]
+    },
+    EventDefinition {
+        id: "enterprise_custom_feature",
+        title: "A big enterprise wants a custom feature",
+        triggers: &[Trigger::Direction(CompanyDirection::B2B), Trigger::MinCustomers(5)],
+        weekly_chance: 8,
+        once: false,
+        choices: &[Choice { label: "Build it for them", effects: &[Effect::AddAccount(Segment::Enterprise, 1000), Effect::TechnicalDebt(10)] },
+                   Choice { label: "Stick to the roadmap", effects: &[] }]
+    },
+    EventDefinition {
+        id: "cloud_price_rise",
+        title: "Our cloud provider raises prices",
+        triggers: &[Trigger::MinCustomers(1)],
+        weekly_chance: 5,
+        once: false,
+        choices: &[Choice { label: "Pass it on to customers", effects: &[Effect::CustomerSatisfaction(-10)] },
+                   Choice { label: "Migrate to another provider", effects: &[Effect::RemoveCash(10), Effect::Reliability(-10)] },
+                   Choice { label: "Absorb the cost", effects: &[Effect::RemoveCash(20)] }]
+    },
+    EventDefinition {
+        id: "critical_vulnerability",
+        title: "Critical vulnerability found in a dependency",
+        triggers: &[Trigger::MinSecurityRisk(30)],
+        weekly_chance: 30,
+        once: false,
+        choices: &[Choice { label: "Drop everything and upgrade", effects: &[Effect::UpgradeDependencies, Effect::RemoveCash(5)] },
+                   Choice { label: "Hope nobody notices", effects: &[Effect::Reliability(-15), Effect::CustomerSatisfaction(-5)] }]
+    },
+    EventDefinition {
+        id: "recession_layoffs",
+        title: "Recession - the board asks for cost cuts",
+        triggers: &[Trigger::Phase(EconomicPhase::Recession), Trigger::MinEmployees(10)],
+        weekly_chance: 20,
+        once: false,
+        choices: &[Choice { label: "Let the weakest performer go", effects: &[Effect::LoseWeakestEmployee] },
+                   Choice { label: "Hold your nerve", effects: &[] }]
+    },
+    EventDefinition {
+        id: "dotcom_conference",
+        title: "Invitation to speak at a big tech conference",
+        triggers: &[Trigger::From(2000, 20), Trigger::MinCash(1000)],
+        weekly_chance: 1000,
+        once: true,
+        choices: &[Choice { label: "Sponsor a booth too", effects: &[Effect::RemoveCash(10), Effect::AddCustomers(Segment::SMB, 20)] },
+                   Choice { label: "Just give the talk", effects: &[Effect::AddCustomers(Segment::SMB, 5)] }]
+    },
+    EventDefinition {
+        id: "acquisition_offer",
+        title: "A bigger company wants to buy us",
+        triggers: &[Trigger::MinValuation(250000)],
+        weekly_chance: 5,
+        once: false,
+        choices: &[Choice { label: "Sell the company", effects: &[Effect::Acquired] },
+                   Choice { label: "Stay independent", effects: &[] }]
+    }
+];
+
+// Snapshot of the world for checking triggers
+//
+pub struct EventContext {
+    pub employees: u16,
+    pub developers: u16,
+    pub customers: u32,
+    pub cash: u32,
+    pub security_risk: u16,
+    pub valuation: u32,
+    pub direction: CompanyDirection,
+    pub phase: EconomicPhase,
+    pub yearweek: YearWeek
+}
+
+impl Trigger {
+    fn holds(&self, context: &EventContext) -> bool {
+        match self {
+            Trigger::MinEmployees(employees) => context.employees >= *employees,
+            Trigger::MinDevelopers(developers) => context.developers >= *developers,
+            Trigger::MinCustomers(customers) => context.customers >= *customers,
+            Trigger::MinCash(cash) => context.cash >= *cash,
+            Trigger::MinSecurityRisk(risk) => context.security_risk >= *risk,
+            Trigger::MinValuation(valuation) => context.valuation >= *valuation,
+            Trigger::Direction(direction) => context.direction == *direction,
+            Trigger::Phase(phase) => context.phase == *phase,
+            Trigger::From(year, week) => YearWeek::new(*year, *week).is_some_and(|from| context.yearweek >= from)
+        }
+    }
+}
+
+// An event waiting on the player
+//
+pub struct PendingEvent {
+    _event: usize,                  // index into EVENTS
+    _raised: YearWeek
+}
+
+impl PendingEvent {
+
+    pub fn definition(&self) -> &'static EventDefinition {
+        &EVENTS[self._event]
+    }
+
+    pub fn raised(&self) -> YearWeek {
+        self._raised
+    }
+}
+
+pub struct EventLogEntry {
+    pub yearweek: YearWeek,
+    pub title: &'static str,
+    pub outcome: &'static str
+}
+
+
+pub struct Events {
+    _pending: Vec<PendingEvent>,
+    _log: Vec<EventLogEntry>,
+    _happened: Vec<&'static str>,   // ids of events that have fired
+    _scheduled: Vec<(usize, YearWeek)> // events a scenario has booked in, whatever the triggers say
+}
+
+impl Events {
+
+    pub fn new() -> Events {
+        Events { _pending: Vec::new(), _log: Vec::new(), _happened: Vec::new(), _scheduled: Vec::new() }
+    }
+
+    pub fn pending(&self) -> &Vec<PendingEvent> {
+        &self._pending
+    }
+
+    // Newest first
+    //
+    pub fn log(&self) -> &Vec<EventLogEntry> {
+        &self._log
+    }
+
+    // Book an event in for a given week - false if there's no such event
+    //
+    pub fn schedule(&mut self, id: &str, yearweek: YearWeek) -> bool {
+        match EVENTS.iter().position(|event| event.id == id) {
+            Some(index) => {
+                self._scheduled.push((index, yearweek));
+                true
+            },
+            None => false
+        }
+    }
+
+    // Weekly check of every event against the world. Returns the number raised.
+    //
+    pub fn check_triggers<R: Rng>(&mut self, context: &EventContext, rng: &mut R) -> usize {
+        let mut raised = 0;
+
+        // Scheduled events happen on the week whether or not their triggers hold - unless the
+        // same event is already waiting on the player
+        //
+        let due: Vec<usize> = self._scheduled.iter().filter(|(_, yearweek)| *yearweek <= context.yearweek).map(|(index, _)| *index).collect();
+        self._scheduled.retain(|(_, yearweek)| *yearweek > context.yearweek);
+
+        for index in due {
+            if self._pending.iter().any(|pending| pending._event == index) {
+                continue;
+            }
+
+            self._pending.push(PendingEvent { _event: index, _raised: context.yearweek });
+            self._happened.push(EVENTS[index].id);
+            raised += 1;
+        }
+
+        for (index, event) in EVENTS.iter().enumerate() {
+            let waiting = self._pending.iter().any(|pending| pending._event == index);
+            let done = event.once && self._happened.contains(&event.id);
+
+            if waiting || done || !event.triggers.iter().all(|trigger| trigger.holds(context)) {
+                continue;
+            }
+
+            if rng.gen_range(0, 1000) < event.weekly_chance {
+                self._pending.push(PendingEvent { _event: index, _raised: context.yearweek });
+                self._happened.push(event.id);
+                raised += 1;
+            }
+        }
+
+        raised
+    }
+
+    // The player picks a choice for the oldest pending event - returns its effects
+    //
+    pub fn resolve(&mut self, choice: usize, yearweek: YearWeek) -> Option<&'static [Effect]> {
+        if self._pending.is_empty() {
+            return None
+        }
+
+        let definition = self._pending[0].definition();
+        if choice >= definition.choices.len() {
+            return None
+        }
+
+        self._pending.remove(0);
+        Some(self.record(definition, choice, yearweek))
+    }
+
+    // Events nobody decided on in time take their default choice
+    //
+    pub fn expire(&mut self, yearweek: YearWeek) -> Vec<&'static [Effect]> {
+        let mut effects = Vec::new();
+
+        while !self._pending.is_empty() && self._pending[0].raised().difference_weeks(&yearweek) >= DECISION_WEEKS {
+            let definition = self._pending.remove(0).definition();
+            effects.push(self.record(definition, definition.choices.len() - 1, yearweek));
+        }
+
+        effects
+    }
+
+    fn record(&mut self, definition: &'static EventDefinition, choice: usize, yearweek: YearWeek) -> &'static [Effect] {
+        self._log.insert(0, EventLogEntry { yearweek, title: definition.title, outcome: definition.choices[choice].label });
+        self._log.truncate(LOG_LENGTH);
+
+        definition.choices[choice].effects
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    fn context(yearweek: YearWeek) -> EventContext {
+        EventContext { employees: 4, developers: 3, customers: 0, cash: 500, security_risk: 50, valuation: 0,
+                       direction: CompanyDirection::B2B, phase: EconomicPhase::Expansion, yearweek }
+    }
+
+    #[test]
+    fn events_trigger_test() {
+        let mut events = Events::new();
+        let mut rng = StepRng::new(0, 0);
+
+        // Developers and the security risk are enough for two events
+        assert_eq!(events.check_triggers(&context(YearWeek::new(2000, 1).unwrap()), &mut rng), 2);
+        assert_eq!(events.pending()[0].definition().id, "competing_offer");
+
+        // Nothing fires twice while it's waiting
+        assert_eq!(events.check_triggers(&context(YearWeek::new(2000, 2).unwrap()), &mut rng), 0);
+    }
+
+    #[test]
+    fn events_resolve_test() {
+        let mut events = Events::new();
+        let mut rng = StepRng::new(0, 0);
+        events.check_triggers(&context(YearWeek::new(2000, 1).unwrap()), &mut rng);
+
+        assert!(events.resolve(5, YearWeek::new(2000, 1).unwrap()).is_none());
+        assert_eq!(events.resolve(0, YearWeek::new(2000, 1).unwrap()), Some(&[Effect::RaiseKeyEmployee(20)][..]));
+        assert_eq!(events.log()[0].outcome, "Match the offer (+20% pay)");
+
+        // The vulnerability is left to expire and we hope nobody notices
+        assert!(events.expire(YearWeek::new(2000, 4).unwrap()).is_empty());
+        assert_eq!(events.expire(YearWeek::new(2000, 5).unwrap()).len(), 1);
+        assert_eq!(events.log()[0].outcome, "Hope nobody notices");
+        assert!(events.pending().is_empty());
+    }
+
+    #[test]
+    fn events_schedule_test() {
+        let mut events = Events::new();
+        let mut rng = StepRng::new(u64::MAX, 0);
+
+        assert!(!events.schedule("alien_invasion", YearWeek::new(2000, 2).unwrap()));
+        assert!(events.schedule("dotcom_conference", YearWeek::new(2000, 2).unwrap()));
+
+        // A high roll never fires anything by chance
+        assert_eq!(events.check_triggers(&context(YearWeek::new(2000, 1).unwrap()), &mut rng), 0);
+        assert_eq!(events.check_triggers(&context(YearWeek::new(2000, 2).unwrap()), &mut rng), 1);
+        assert_eq!(events.pending()[0].definition().id, "dotcom_conference");
+        assert_eq!(events.check_triggers(&context(YearWeek::new(2000, 3).unwrap()), &mut rng), 0);
+
+        // Booked in while the same event is already waiting - it isn't raised twice
+        let mut events = Events::new();
+        events.schedule("competing_offer", YearWeek::new(2000, 2).unwrap());
+        events.check_triggers(&context(YearWeek::new(2000, 1).unwrap()), &mut StepRng::new(0, 0));
+        assert_eq!(events.check_triggers(&context(YearWeek::new(2000, 2).unwrap()), &mut rng), 0);
+        assert_eq!(events.pending().iter().filter(|pending| pending.definition().id == "competing_offer").count(), 1);
+    }
+
+}
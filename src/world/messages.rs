@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use super::timeframe::YearWeek;
+
+// Messages kept before the oldest are dropped
+//
+const MESSAGE_CAPACITY: usize = 500;
+
+// In order of how much attention a message needs - good news can wait
+//
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Severity {
+    Good,
+    Info,
+    Warning,
+    Critical
+}
+
+impl Severity {
+
+    // Cycle the minimum severity filter
+    //
+    pub fn next(&self) -> Severity {
+        match self {
+            Severity::Good => Severity::Info,
+            Severity::Info => Severity::Warning,
+            Severity::Warning => Severity::Critical,
+            Severity::Critical => Severity::Good
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Category {
+    Customers,
+    Sales,
+    Finance,
+    People,
+    Product,
+    Market,
+    Economy,
+    Events,
+    Achievements
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub seq: u64,
+    pub yearweek: YearWeek,         // game time it happened
+    pub severity: Severity,
+    pub category: Category,
+    pub text: String
+}
+
+
+// Everything the simulation has to say. The TUI renders it as a log and headless
+// consumers can poll for whatever arrived since the last sequence number they saw.
+//
+pub struct MessageBus {
+    _messages: VecDeque<Message>,
+    _next_seq: u64
+}
+
+impl MessageBus {
+
+    pub fn new() -> MessageBus {
+        MessageBus { _messages: VecDeque::new(), _next_seq: 1 }
+    }
+
+    pub fn emit(&mut self, yearweek: YearWeek, severity: Severity, category: Category, text: String) {
+        self._messages.push_back(Message { seq: self._next_seq, yearweek, severity, category, text });
+        self._next_seq += 1;
+
+        if self._messages.len() > MESSAGE_CAPACITY {
+            self._messages.pop_front();
+        }
+    }
+
+    // Oldest first
+    //
+    pub fn messages(&self) -> &VecDeque<Message> {
+        &self._messages
+    }
+
+    // Newest first, at or above a severity
+    //
+    pub fn filtered(&self, min_severity: Severity) -> Vec<&Message> {
+        self._messages.iter().rev().filter(|message| message.severity >= min_severity).collect()
+    }
+
+    // Everything after the given sequence number, oldest first
+    //
+    pub fn since(&self, seq: u64) -> Vec<&Message> {
+        self._messages.iter().filter(|message| message.seq > seq).collect()
+    }
+
+    pub fn latest_seq(&self) -> u64 {
+        self._next_seq - 1
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn message_bus_test() {
+        let mut bus = MessageBus::new();
+        let yearweek = YearWeek::new(2000, 1).unwrap();
+
+        bus.emit(yearweek, Severity::Info, Category::Customers, "Gained 1 customer".to_string());
+        let seen = bus.latest_seq();
+        bus.emit(yearweek, Severity::Critical, Category::Finance, "Payroll failed".to_string());
+        bus.emit(yearweek, Severity::Warning, Category::Market, "Initech cut prices".to_string());
+
+        assert_eq!(bus.since(seen).len(), 2);
+        assert_eq!(bus.since(seen)[0].text, "Payroll failed");
+
+        let warnings = bus.filtered(Severity::Warning);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].category, Category::Market);
+
+        // Good news is the first thing to go
+        bus.emit(yearweek, Severity::Good, Category::Sales, "Won a deal".to_string());
+        assert_eq!(bus.filtered(Severity::Good).len(), 4);
+        assert_eq!(bus.filtered(Severity::Info).len(), 3);
+    }
+
+    #[test]
+    fn message_bus_capacity_test() {
+        let mut bus = MessageBus::new();
+
+        for message in 0..MESSAGE_CAPACITY + 10 {
+            bus.emit(YearWeek::new(2000, 1).unwrap(), Severity::Info, Category::Product, message.to_string());
+        }
+
+        assert_eq!(bus.messages().len(), MESSAGE_CAPACITY);
+        assert_eq!(bus.messages()[0].seq, 11);
+        assert_eq!(bus.latest_seq(), MESSAGE_CAPACITY as u64 + 10);
+    }
+
+}
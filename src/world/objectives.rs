@@ -0,0 +1,245 @@
+use serde::Deserialize;
+use std::fmt;
+
+use crate::company::Ownership;
+use super::timeframe::YearWeek;
+
+// Valuation the markets want to see before they'll take us public
+//
+pub const IPO_MIN_VALUATION: u32 = 2000000;
+
+// Snapshot of the game for checking objectives
+//
+pub struct ObjectiveContext {
+    pub customers: u32,
+    pub cash: u32,
+    pub monthly_revenue: u32,
+    pub employees: u16,
+    pub funding_raised: u32,
+    pub valuation: u32,
+    pub profitable_months: u16,         // current streak
+    pub ownership: Ownership,
+    pub start_yearweek: YearWeek,
+    pub yearweek: YearWeek
+}
+
+// Anything the game can be won or lost on. Progress is 0 - 100 and the objective is
+// met at 100, which is what lets a game that ends early still be scored.
+//
+pub trait Objective {
+    fn describe(&self) -> String;
+
+    fn progress(&self, context: &ObjectiveContext) -> u16;
+
+    fn met(&self, context: &ObjectiveContext) -> bool {
+        self.progress(context) >= 100
+    }
+}
+
+// The objectives scenario files and presets can declare
+//
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    Customers { min: u32 },
+    Cash { min: u32 },
+    MonthlyRevenue { min: u32 },
+    Employees { min: u16 },
+    FundingRaised { min: u32 },
+    Valuation { min: u32 },
+    ProfitableMonths { min: u16 },
+    Ipo,
+    Acquired,
+    Date { year: i32, week: u32 },      // reached this week - a deadline when it's a loss
+    TimeLimit { weeks: u32 },           // weeks since the start
+    AnyOf { of: Vec<Condition> }
+}
+
+fn percentage(value: u32, target: u32) -> u16 {
+    match target {
+        0 => 100,
+        _ => u32::min(100, (value as u64 * 100 / target as u64) as u32) as u16
+    }
+}
+
+impl Condition {
+
+    // Dates have to exist in the calendar
+    //
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Condition::Date { year, week } if YearWeek::new(*year, *week).is_none() => Err(format!("{} has no week {}", year, week)),
+            Condition::AnyOf { of } => of.iter().try_for_each(|condition| condition.validate()),
+            _ => Ok(())
+        }
+    }
+}
+
+impl Objective for Condition {
+
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+
+    fn progress(&self, context: &ObjectiveContext) -> u16 {
+        let weeks_played = context.start_yearweek.difference_weeks(&context.yearweek);
+
+        match self {
+            Condition::Customers { min } => percentage(context.customers, *min),
+            Condition::Cash { min } => percentage(context.cash, *min),
+            Condition::MonthlyRevenue { min } => percentage(context.monthly_revenue, *min),
+            Condition::Employees { min } => percentage(context.employees as u32, *min as u32),
+            Condition::FundingRaised { min } => percentage(context.funding_raised, *min),
+            Condition::Valuation { min } => percentage(context.valuation, *min),
+            Condition::ProfitableMonths { min } => percentage(context.profitable_months as u32, *min as u32),
+
+            // Only going public counts - the valuation shows how close we are
+            //
+            Condition::Ipo => match context.ownership {
+                Ownership::Public => 100,
+                _ => u16::min(99, percentage(context.valuation, IPO_MIN_VALUATION))
+            },
+            Condition::Acquired => if context.ownership == Ownership::Acquired { 100 } else { 0 },
+            Condition::Date { year, week } => match YearWeek::new(*year, *week) {
+                Some(date) if context.yearweek >= date => 100,
+                Some(date) => u16::min(99, percentage(weeks_played, context.start_yearweek.difference_weeks(&date))),
+                None => 0
+            },
+            Condition::TimeLimit { weeks } => percentage(weeks_played, *weeks),
+            Condition::AnyOf { of } => of.iter().map(|condition| condition.progress(context)).max().unwrap_or(0)
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Condition::Customers { min } => write!(f, "{} customers", min),
+            Condition::Cash { min } => write!(f, "{} in the bank", min),
+            Condition::MonthlyRevenue { min } => write!(f, "{} a month recurring revenue", min),
+            Condition::Employees { min } => write!(f, "{} employees", min),
+            Condition::FundingRaised { min } => write!(f, "{} raised from investors", min),
+            Condition::Valuation { min } => write!(f, "valued at {}", min),
+            Condition::ProfitableMonths { min } => write!(f, "{} profitable months in a row", min),
+            Condition::Ipo => write!(f, "go public"),
+            Condition::Acquired => write!(f, "get acquired"),
+            Condition::Date { year, week } => write!(f, "{:04}-{:02} arrives", year, week),
+            Condition::TimeLimit { weeks } => write!(f, "{} weeks pass", weeks),
+            Condition::AnyOf { of } => write!(f, "{}", of.iter().map(|condition| condition.to_string()).collect::<Vec<String>>().join(" or "))
+        }
+    }
+}
+
+// One line of the scorecard - an objective and how far along it we are
+//
+pub struct ScorecardEntry {
+    pub description: String,
+    pub progress: u16,                  // 0 - 100
+    pub win: bool                       // a win objective rather than a way to lose
+}
+
+// Where we stand on every objective. The score is the average progress on the
+// win objectives so a near miss still counts for something.
+//
+pub struct Scorecard {
+    pub entries: Vec<ScorecardEntry>,
+    pub score: u16
+}
+
+// Win when every win objective is met, lose as soon as any loss objective is
+//
+pub struct Objectives {
+    _win: Vec<Box<dyn Objective>>,
+    _lose: Vec<Box<dyn Objective>>
+}
+
+impl Objectives {
+
+    pub fn new() -> Objectives {
+        Objectives { _win: Vec::new(), _lose: Vec::new() }
+    }
+
+    pub fn from_conditions(win: &[Condition], lose: &[Condition]) -> Objectives {
+        let mut objectives = Objectives::new();
+        win.iter().for_each(|condition| objectives.add_win(Box::new(condition.clone())));
+        lose.iter().for_each(|condition| objectives.add_lose(Box::new(condition.clone())));
+        objectives
+    }
+
+    pub fn add_win(&mut self, objective: Box<dyn Objective>) {
+        self._win.push(objective);
+    }
+
+    pub fn add_lose(&mut self, objective: Box<dyn Objective>) {
+        self._lose.push(objective);
+    }
+
+    pub fn won(&self, context: &ObjectiveContext) -> bool {
+        !self._win.is_empty() && self._win.iter().all(|objective| objective.met(context))
+    }
+
+    // What lost us the game
+    //
+    pub fn lost(&self, context: &ObjectiveContext) -> Option<String> {
+        self._lose.iter().find(|objective| objective.met(context)).map(|objective| objective.describe())
+    }
+
+    pub fn scorecard(&self, context: &ObjectiveContext) -> Scorecard {
+        let entry = |objective: &dyn Objective, win: bool| ScorecardEntry { description: objective.describe(), progress: objective.progress(context), win };
+
+        let entries: Vec<ScorecardEntry> = self._win.iter().map(|objective| entry(objective.as_ref(), true))
+                                                .chain(self._lose.iter().map(|objective| entry(objective.as_ref(), false)))
+                                                .collect();
+
+        let score = match self._win.len() {
+            0 => 0,
+            wins => ( entries.iter().filter(|entry| entry.win).map(|entry| entry.progress as u32).sum::<u32>() / wins as u32 ) as u16
+        };
+
+        Scorecard { entries, score }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn context(customers: u32, funding_raised: u32, yearweek: YearWeek) -> ObjectiveContext {
+        ObjectiveContext { customers, cash: 0, monthly_revenue: 0, employees: 4, funding_raised, valuation: 1000000,
+                           profitable_months: 0, ownership: Ownership::Private, start_yearweek: YearWeek::new(2000, 1).unwrap(), yearweek }
+    }
+
+    #[test]
+    fn objectives_test() {
+        let objectives = Objectives::from_conditions(&[Condition::Customers { min: 500 }],
+                                                     &[Condition::Date { year: 2003, week: 1 }, Condition::FundingRaised { min: 1 }]);
+        let start = YearWeek::new(2000, 1).unwrap();
+
+        assert!(!objectives.won(&context(499, 0, start)));
+        assert!(objectives.won(&context(500, 0, start)));
+        assert_eq!(objectives.lost(&context(500, 0, start)), None);
+        assert_eq!(objectives.lost(&context(0, 1000, start)), Some("1 raised from investors".to_string()));
+        assert_eq!(objectives.lost(&context(0, 0, YearWeek::new(2003, 1).unwrap())), Some("2003-01 arrives".to_string()));
+
+        // Sandbox games without objectives can't be won
+        assert!(!Objectives::new().won(&context(500, 0, start)));
+    }
+
+    #[test]
+    fn scorecard_test() {
+        let objectives = Objectives::from_conditions(&[Condition::Customers { min: 500 },
+                                                       Condition::AnyOf { of: vec![Condition::Ipo, Condition::Acquired] }],
+                                                     &[Condition::TimeLimit { weeks: 104 }]);
+
+        let scorecard = objectives.scorecard(&context(250, 0, YearWeek::new(2001, 1).unwrap()));
+        assert_eq!(scorecard.entries.len(), 3);
+        assert_eq!(scorecard.entries[0].progress, 50);
+        assert_eq!(scorecard.entries[1].description, "go public or get acquired");
+        assert_eq!(scorecard.entries[1].progress, 50);
+        assert_eq!(scorecard.entries[2].progress, 50);
+        assert_eq!(scorecard.score, 50);
+    }
+
+}
@@ -0,0 +1,79 @@
+// Weeks a game can take before there's no bonus left for speed
+//
+const TIME_BONUS_WEEKS: u32 = 520;
+
+// Points for each point of average morale and customer satisfaction
+//
+const WELLBEING_POINTS: u32 = 50;
+const SATISFACTION_POINTS: u32 = 50;
+
+// Everything the score is made from
+//
+pub struct ScoreContext {
+    pub valuation: u32,
+    pub weeks_played: u32,
+    pub morale: u16,                    // 0 - 100 average across the company
+    pub customer_satisfaction: u16,     // 0 - 100
+    pub achievement_points: u32,
+    pub won: bool
+}
+
+// The score broken down so the player can see where it came from
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Score {
+    pub valuation: u32,
+    pub wellbeing: u32,
+    pub satisfaction: u32,
+    pub achievements: u32,
+    pub time_bonus: u32,                // percentage on top for a quick win
+    pub total: u32
+}
+
+impl Score {
+
+    // A company worth a lot with happy people and happy customers scores well, and
+    // winning quickly scores better still
+    //
+    pub fn calculate(context: &ScoreContext) -> Score {
+        let valuation = context.valuation / 100;
+        let wellbeing = context.morale as u32 * WELLBEING_POINTS;
+        let satisfaction = context.customer_satisfaction as u32 * SATISFACTION_POINTS;
+        let achievements = context.achievement_points;
+
+        let time_bonus = match context.won {
+            true => ( TIME_BONUS_WEEKS - u32::min(TIME_BONUS_WEEKS, context.weeks_played) ) * 100 / TIME_BONUS_WEEKS,
+            false => 0
+        };
+
+        let subtotal = valuation as u64 + wellbeing as u64 + satisfaction as u64 + achievements as u64;
+        let total = u64::min(u32::MAX as u64, subtotal * ( 100 + time_bonus as u64 ) / 100) as u32;
+
+        Score { valuation, wellbeing, satisfaction, achievements, time_bonus, total }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn score_test() {
+        let mut context = ScoreContext { valuation: 1000000, weeks_played: 260, morale: 80, customer_satisfaction: 60,
+                                         achievement_points: 1500, won: false };
+
+        let score = Score::calculate(&context);
+        assert_eq!(score.valuation, 10000);
+        assert_eq!(score.wellbeing, 4000);
+        assert_eq!(score.satisfaction, 3000);
+        assert_eq!(score.total, 18500);
+
+        // Winning in five years gets half as much again
+        context.won = true;
+        assert_eq!(Score::calculate(&context).time_bonus, 50);
+        assert_eq!(Score::calculate(&context).total, 27750);
+    }
+
+}
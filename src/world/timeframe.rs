@@ -1,84 +1,248 @@
 
-use chrono::{DateTime, Local, NaiveDate, Weekday, Datelike};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use std::fmt;
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub struct YearWeek {
-    _year: i32,
-    _week: u32
+    _year: i32,                     // ISO year
+    _week: u32                      // ISO week - 1 to 52 or 53
 }
 
+// Where a game starts unless it's set up otherwise
+//
+pub const START_YEARWEEK: YearWeek = YearWeek { _year: 2000, _week: 1 };
+
+// Ticks of game time in a week unless it's set up otherwise
+//
+pub const DEFAULT_TICKS_PER_WEEK: u16 = 2;
+
+// What the calendar did when the week turned - the period that just ended is
+// carried along so payroll, billing and reporting know what they're closing
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Rollover {
+    Week,
+    Month(u32),
+    Quarter(i32, u32),              // year, quarter
+    Year(i32)
+}
 
+// The game runs on ISO weeks. A week belongs to the month, quarter and year its
+// Monday falls in, so a week 1 starting in December is still part of the old year.
+//
 impl YearWeek {
-    pub fn new(year: i32, week: u32) -> YearWeek {
-        assert!(week >=1 && week <= 52);
-        YearWeek { _year: year, _week: week }
+
+    // None if the week doesn't exist in that year
+    //
+    pub fn new(year: i32, week: u32) -> Option<YearWeek> {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+        Some(YearWeek { _year: year, _week: week })
+    }
+
+    pub fn from_date(date: NaiveDate) -> YearWeek {
+        let iso_week = date.iso_week();
+        YearWeek { _year: iso_week.year(), _week: iso_week.week() }
+    }
+
+    // 52 or 53 - the week with the 28th of December in it is always the last
+    //
+    pub fn weeks_in_year(year: i32) -> u32 {
+        NaiveDate::from_ymd_opt(year, 12, 28).map_or(52, |date| date.iso_week().week())
+    }
+
+    pub fn year(&self) -> i32 {
+        self._year
+    }
+
+    pub fn week(&self) -> u32 {
+        self._week
+    }
+
+    // Only built from valid weeks so this always exists
+    //
+    pub fn monday(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self._year, self._week, Weekday::Mon).unwrap()
+    }
+
+    pub fn month(&self) -> u32 {
+        self.monday().month()
+    }
+
+    pub fn quarter(&self) -> u32 {
+        ( self.month() - 1 ) / 3 + 1
+    }
+
+    pub fn calendar_year(&self) -> i32 {
+        self.monday().year()
+    }
+
+    pub fn add_weeks(&self, weeks: u32) -> YearWeek {
+        YearWeek::from_date(self.monday() + Duration::weeks(weeks as i64))
     }
 
     pub fn difference_weeks(&self, year_week: &YearWeek) -> u32 {
-        i32::abs_diff(self._year * 52 + self._week as i32, year_week._year * 52 + year_week._week as i32) 
+        ( self.monday() - year_week.monday() ).num_weeks().unsigned_abs() as u32
     }
 
-    // Returns true if the month rotates to cause a payroll run
+    // Move on a week and return everything that rolled over, smallest period first
     //
-    pub fn increment_week(&mut self) -> bool {
+    pub fn increment_week(&mut self) -> Vec<Rollover> {
+        let before = *self;
+        *self = self.add_weeks(1);
 
-        //println!("creating before_date = {}/{}",self._year, self._week);
+        let mut rollovers = vec![Rollover::Week];
 
-        let before_date = NaiveDate::from_isoywd(self._year, self._week, Weekday::Mon);
-        //println!("before_date = {}",before_date);
+        if before.month() != self.month() {
+            rollovers.push(Rollover::Month(before.month()));
+        }
 
-        if self._week < 52 {
-            self._week += 1;
-        } else  {
-            self._week = 1;
-            self._year += 1;
+        if before.quarter() != self.quarter() {
+            rollovers.push(Rollover::Quarter(before.calendar_year(), before.quarter()));
         }
 
-        let after_date = NaiveDate::from_isoywd(self._year, self._week, Weekday::Mon);
-        //println!("after_date = {}",after_date);
+        if before.calendar_year() != self.calendar_year() {
+            rollovers.push(Rollover::Year(before.calendar_year()));
+        }
+
+        return rollovers
+    }
+
+}
 
-        return before_date.month() != after_date.month()
+impl fmt::Display for YearWeek {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self._year, self._week)
     }
+}
+
+// Ticks we'll run between two frames at most, so input and rendering keep up
+//
+pub const MAX_TICKS_PER_FRAME: u32 = 20;
+
+// How fast the game clock runs relative to the base tick time
+//
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GameSpeed {
+    Normal,
+    Double,
+    Fast,
+    Max
+}
+
+impl GameSpeed {
 
-    pub fn to_string(&self) -> String {
-        format!("{:04}-{:02}", self._year, self._week)
+    pub fn faster(&self) -> GameSpeed {
+        match self {
+            GameSpeed::Normal => GameSpeed::Double,
+            GameSpeed::Double => GameSpeed::Fast,
+            GameSpeed::Fast | GameSpeed::Max => GameSpeed::Max
+        }
     }
 
-    
+    pub fn slower(&self) -> GameSpeed {
+        match self {
+            GameSpeed::Max => GameSpeed::Fast,
+            GameSpeed::Fast => GameSpeed::Double,
+            GameSpeed::Double | GameSpeed::Normal => GameSpeed::Normal
+        }
+    }
+
+    // None is as fast as we can go
+    //
+    fn multiplier(&self) -> Option<u32> {
+        match self {
+            GameSpeed::Normal => Some(1),
+            GameSpeed::Double => Some(2),
+            GameSpeed::Fast => Some(5),
+            GameSpeed::Max => None
+        }
+    }
+}
+
+impl fmt::Display for GameSpeed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.multiplier() {
+            Some(multiplier) => write!(f, "{}x", multiplier),
+            None => write!(f, "max")
+        }
+    }
 }
 
 pub struct Timeframe {
-    _speed: u16,                          // 1-1000 - millisecond loop time (lower is faster)
+    _speed: u16,                          // 1-1000 - milliseconds per tick at normal speed (lower is faster)
+    _game_speed: GameSpeed,               // multiplier on top of _speed
+    _paused: bool,
     _game_ticks: u32,                     // how far we're into the game
     _last_tick_time: DateTime<Local>,     // where we are now
     _game_start_time: DateTime<Local>,    // when did the game start?
     _ticks_per_week: u16,                 // ticks of game time in a week
-    _weeks_per_year: u16,                 // number of weeks in a year
     _start_yearweek: YearWeek,            // week we started
     _current_yearweek: YearWeek,          // current year
 }
 
 impl Timeframe {
-    pub fn new(speed: u16, game_ticks: u32) -> Timeframe {
+    pub fn new(speed: u16, game_ticks: u32, start_yearweek: YearWeek, ticks_per_week: u16) -> Timeframe {
         Timeframe { _speed: speed,
+                    _game_speed: GameSpeed::Normal,
+                    _paused: false,
                     _game_ticks: game_ticks,
                     _last_tick_time: Local::now(),
                     _game_start_time: Local::now(),
-                    _ticks_per_week: 2,
-                    _weeks_per_year: 52,
-                    _start_yearweek: YearWeek::new(2000, 1),
-                    _current_yearweek: YearWeek::new(2000, 1)
+                    _ticks_per_week: u16::max(1, ticks_per_week),
+                    _start_yearweek: start_yearweek,
+                    _current_yearweek: start_yearweek
                   }
     }
 
+    #[allow(dead_code)]
     pub fn speed(&self) -> u16 {
         self._speed
     }
 
+    pub fn game_speed(&self) -> GameSpeed {
+        self._game_speed
+    }
+
+    pub fn set_game_speed(&mut self, game_speed: GameSpeed) {
+        self._game_speed = game_speed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self._paused
+    }
+
+    // Time doesn't build up while we're paused
+    //
+    pub fn set_paused(&mut self, paused: bool, time_now: DateTime<Local>) {
+        self._paused = paused;
+        self._last_tick_time = time_now;
+    }
+
+    // How many ticks are owed since the last one - independent of how often we poll
+    //
+    pub fn ticks_due(&self, time_now: DateTime<Local>) -> u32 {
+        if self._paused {
+            return 0
+        }
+
+        let interval = match self._game_speed.multiplier() {
+            Some(multiplier) => u32::max(1, self._speed as u32 / multiplier),
+            None => return MAX_TICKS_PER_FRAME
+        };
+
+        let elapsed = (time_now - self._last_tick_time).num_milliseconds();
+        if elapsed <= 0 {
+            return 0
+        }
+
+        return u32::min(MAX_TICKS_PER_FRAME, elapsed as u32 / interval)
+    }
+
     pub fn game_ticks(&self) -> u32 {
         self._game_ticks
     }
 
+    #[allow(dead_code)]
     pub fn last_tick_time(&self) -> DateTime<Local> {
         self._last_tick_time
     }
@@ -87,14 +251,16 @@ impl Timeframe {
         self._ticks_per_week
     }
     
-    pub fn increment_game_ticks(&mut self) -> bool {
+    // Returns whatever the calendar rolled over - nothing unless a new week started
+    //
+    pub fn increment_game_ticks(&mut self) -> Vec<Rollover> {
         self._game_ticks += 1;
 
-        if self._game_ticks % self._ticks_per_week as u32 == 0 {
+        if self._game_ticks.is_multiple_of(self._ticks_per_week as u32) {
             return self._current_yearweek.increment_week()
         }
 
-        return false
+        return Vec::new()
     }
 
     pub fn set_current_time(&mut self, time_now: DateTime<Local>) {
@@ -109,12 +275,16 @@ impl Timeframe {
         self._current_yearweek
     }
 
+    pub fn get_start_yearweek(&self) -> YearWeek {
+        self._start_yearweek
+    }
+
     pub fn get_current_month(&self)  -> u32 {
-        return NaiveDate::from_isoywd(self._current_yearweek._year, self._current_yearweek._week, Weekday::Mon).month()
+        return self._current_yearweek.month()
     }
 
     pub fn get_current_year(&self) -> i32 {
-        return self._current_yearweek._year
+        return self._current_yearweek.calendar_year()
     }
 
     pub fn get_current_week(&self) -> u32 {
@@ -132,34 +302,76 @@ mod test {
     #[test]
     fn timeframe_tests() {
 
-        let timeframe = Timeframe::new(100, 100);
+        let timeframe = Timeframe::new(100, 100, START_YEARWEEK, DEFAULT_TICKS_PER_WEEK);
         assert_eq!(timeframe.ticks_per_week() , 2);
  
     }
 
     #[test]
     fn year_week_test() {
-        let year_week = YearWeek::new(2000, 1);
+        let year_week = YearWeek::new(2000, 1).unwrap();
         assert_eq!(year_week.to_string(), "2000-01");
+
+        // 2004 has a week 53, 2005 doesn't
+        assert!(YearWeek::new(2004, 53).is_some());
+        assert!(YearWeek::new(2005, 53).is_none());
+        assert!(YearWeek::new(2005, 0).is_none());
+        assert_eq!(YearWeek::weeks_in_year(2004), 53);
     }
 
     #[test]
     fn year_week_difference_test() {
-        let year_week_1 = YearWeek::new(2000, 1);
-        let year_week_2 = YearWeek::new(2010, 40);
-        println!("Difference = {}",year_week_1.difference_weeks(&year_week_2));
-        assert_eq!(year_week_1.difference_weeks(&year_week_2) , 559);
+        let year_week_1 = YearWeek::new(2000, 1).unwrap();
+        let year_week_2 = YearWeek::new(2010, 40).unwrap();
+
+        // 2004 and 2009 both have 53 weeks
+        assert_eq!(year_week_1.difference_weeks(&year_week_2) , 561);
+        assert_eq!(year_week_2.difference_weeks(&year_week_1) , 561);
+        assert_eq!(year_week_1.add_weeks(561), year_week_2);
+    }
+
+    #[test]
+    fn year_week_rollover_test() {
+        let mut year_week = YearWeek::new(2004, 52).unwrap();
+        assert_eq!(year_week.increment_week(), vec![Rollover::Week]);
+        assert_eq!(year_week.to_string(), "2004-53");
+
+        // Week 1 of 2005 starts on the 3rd of January
+        assert_eq!(year_week.increment_week(), vec![Rollover::Week, Rollover::Month(12), Rollover::Quarter(2004, 4), Rollover::Year(2004)]);
+        assert_eq!(year_week.to_string(), "2005-01");
+        assert_eq!(year_week.calendar_year(), 2005);
+
+        // Week 1 of 2004 started on the 29th of December - still 2003
+        let year_week = YearWeek::new(2004, 1).unwrap();
+        assert_eq!(year_week.calendar_year(), 2003);
+        assert_eq!(year_week.month(), 12);
+    }
+
+    #[test]
+    fn ticks_due_test() {
+        let mut timeframe = Timeframe::new(100, 0, START_YEARWEEK, DEFAULT_TICKS_PER_WEEK);
+        let start = timeframe.last_tick_time();
+
+        assert_eq!(timeframe.ticks_due(start + chrono::Duration::milliseconds(250)), 2);
+
+        timeframe.set_game_speed(GameSpeed::Normal.faster().faster());
+        assert_eq!(timeframe.game_speed(), GameSpeed::Fast);
+        assert_eq!(timeframe.ticks_due(start + chrono::Duration::milliseconds(250)), 12);
+        assert_eq!(timeframe.ticks_due(start + chrono::Duration::seconds(10)), MAX_TICKS_PER_FRAME);
+
+        timeframe.set_paused(true, start);
+        assert_eq!(timeframe.ticks_due(start + chrono::Duration::seconds(10)), 0);
     }
 
     #[test]
     fn test_month() {
-        let test_month = Timeframe::new(100, 100);
+        let test_month = Timeframe::new(100, 100, START_YEARWEEK, DEFAULT_TICKS_PER_WEEK);
         assert_eq!(test_month.get_current_month(), 1);
     }
 
     #[test]
     fn test_week() {
-        let test_week: Timeframe = Timeframe::new(100, 100);
+        let test_week: Timeframe = Timeframe::new(100, 100, START_YEARWEEK, DEFAULT_TICKS_PER_WEEK);
         assert_eq!(test_week.get_current_week(), 1);
     }
 